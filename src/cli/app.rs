@@ -3,8 +3,8 @@
 // (CLI) of the ToDo application using the 'clap' crate.
 //
 // 1) The 'build_cli' function returns a Command object that includes all
-//    subcommands (create, read, update, delete, list) and their respective
-//    arguments/flags.
+//    subcommands (create, read, update, delete, list, done, history, ...) and
+//    their respective arguments/flags.
 // 2) Each subcommand corresponds to a user action; the code in main.rs reads the
 //    user's selected subcommand from the CLI and dispatches to the proper handler
 //    in the 'commands' module.
@@ -20,10 +20,14 @@
 //   types from 'models' to actually carry out the requested task.
 //
 // Below is the function that constructs the command line interface,
-// detailing each subcommand and the arguments it accepts.
+// detailing each subcommand and the arguments it accepts, followed by
+// 'with_ids', which adds the task selection arguments, and 'with_selection',
+// which also adds the options shared by bulk subcommands that change tasks.
 ///////////////////////////////////////////////////////////////////////////////
 
-use clap::{command, Arg, Command};
+use clap::builder::PossibleValuesParser;
+use clap::{command, Arg, ArgAction, Command};
+use crate::formats::Format;
 
 pub fn build_cli() -> Command {
     command!()
@@ -37,36 +41,53 @@ pub fn build_cli() -> Command {
                     Arg::new("title")
                         .short('t') 
                         .long("title")//
-                        .required(true)
+                        .required_unless_present("from-file")
                         .help("Task's Title")
                 )
                 .arg(
                     Arg::new("description")
                         .short('d')
                         .long("description")
-                        .required(true)
-                        .help("Task's Description")
+                        .required(false)
+                        .help("Task's Description (use - to read it from stdin)")
                 )
-        )
-        .subcommand(
-            Command::new("read")
-                .about("Reads task's details")
                 .arg(
-                    Arg::new("id")
-                        .help("The ID of the task to read")
-                        .required(true)
-                        .index(1)
+                    Arg::new("from-file")
+                        .short('f')
+                        .long("from-file")
+                        .conflicts_with_all(["title", "description"])
+                        .help("Create one task per line or Markdown checklist item of a file (- for stdin)")
+                )
+                .arg(
+                    Arg::new("tag")
+                        .long("tag")
+                        .action(ArgAction::Append)
+                        .help("Tag to attach to the task (repeatable)")
+                )
+                .arg(
+                    Arg::new("due")
+                        .long("due")
+                        .help("Due date, e.g. 2026-11-03, 'tomorrow 14:00', 'next friday', 'in 3 days' or eom")
+                )
+                .arg(
+                    Arg::new("scheduled")
+                        .long("scheduled")
+                        .help("Date to start working on the task (same forms as --due)")
+                )
+                .arg(
+                    Arg::new("estimate")
+                        .long("estimate")
+                        .help("Expected effort, as a duration (2h, 1h30m) or story points (3pt)")
                 )
         )
-        .subcommand(
+        .subcommand(with_ids(
+            Command::new("read")
+                .about("Reads task's details\nMore info: read --help"),
+            "The IDs of the tasks to read",
+        ))
+        .subcommand(with_selection(
             Command::new("update")
                 .about("Update a task details\nMore info: update --help")
-                .arg(
-                    Arg::new("id")
-                        .help("The ID of the task to read")
-                        .required(true)
-                        .index(1)
-                )
                 .arg(
                     Arg::new("title")
                         .short('t')
@@ -81,19 +102,382 @@ pub fn build_cli() -> Command {
                         .required(false)
                         .help("Task's Description to be Updated")
                 )
-        )
+                .arg(
+                    Arg::new("add-tag")
+                        .long("add-tag")
+                        .action(ArgAction::Append)
+                        .help("Tag to add to the selected tasks (repeatable)")
+                )
+                .arg(
+                    Arg::new("remove-tag")
+                        .long("remove-tag")
+                        .action(ArgAction::Append)
+                        .help("Tag to remove from the selected tasks (repeatable)")
+                )
+                .arg(
+                    Arg::new("due")
+                        .long("due")
+                        .help("New due date, e.g. tomorrow, 'next friday', 'in 3 days' (none to clear)")
+                )
+                .arg(
+                    Arg::new("scheduled")
+                        .long("scheduled")
+                        .help("New scheduled date (same forms as --due, none to clear)")
+                )
+                .arg(
+                    Arg::new("estimate")
+                        .long("estimate")
+                        .help("New estimate, e.g. 2h, 1h30m or 3pt (none to clear)")
+                ),
+            "The IDs of the tasks to update",
+        ))
         .subcommand(
-            Command::new("delete")
-                .about("Delete a task")
+            Command::new("edit")
+                .about("Edit all fields of a task in $VISUAL / $EDITOR")
                 .arg(
                     Arg::new("id")
-                        .help("The ID of the task to delete")
+                        .help("The ID of the task to edit")
                         .required(true)
                         .index(1)
                 )
         )
+        .subcommand(with_selection(
+            Command::new("delete")
+                .about("Delete tasks\nMore info: delete --help"),
+            "The IDs of the tasks to delete",
+        ))
+        .subcommand(with_selection(
+            Command::new("done")
+                .about("Mark tasks as done\nMore info: done --help"),
+            "The IDs of the tasks to mark as done",
+        ))
+        .subcommand(with_selection(
+            Command::new("reopen")
+                .about("Mark done tasks as open again\nMore info: reopen --help"),
+            "The IDs of the tasks to reopen",
+        ))
         .subcommand(
             Command::new("list")
                 .about("List all tasks")
+                .arg(
+                    Arg::new("where")
+                        .short('w')
+                        .long("where")
+                        .help("Only list tasks matching a filter, e.g. 'done:no due.before:eow'")
+                )
+                .arg(
+                    Arg::new("sum")
+                        .long("sum")
+                        .value_parser(PossibleValuesParser::new(["estimate"]))
+                        .help("Add a footer totalling the listed tasks' estimates against the time tracked")
+                )
+        )
+        .subcommand(
+            Command::new("shell")
+                .about("Start an interactive shell that keeps the tasks loaded")
+        )
+        .subcommand(
+            Command::new("import")
+                .about("Import tasks from another file format\nMore info: import --help")
+                .arg(
+                    Arg::new("file")
+                        .help("The file to import (- for stdin)")
+                        .required(true)
+                        .index(1)
+                )
+                .arg(
+                    Arg::new("format")
+                        .long("format")
+                        .default_value("csv")
+                        .value_parser(PossibleValuesParser::new(Format::IMPORT_NAMES))
+                        .help("Format of the file")
+                )
+                .arg(
+                    Arg::new("map")
+                        .long("map")
+                        .help("CSV columns for task fields, e.g. title=Summary,description=Notes,tags=Labels")
+                )
+                .arg(
+                    Arg::new("dry-run")
+                        .long("dry-run")
+                        .action(ArgAction::SetTrue)
+                        .help("Show what would be imported without saving")
+                )
+        )
+        .subcommand(
+            Command::new("export")
+                .about("Export tasks to another file format\nMore info: export --help")
+                .arg(
+                    Arg::new("format")
+                        .long("format")
+                        .default_value("todotxt")
+                        .value_parser(PossibleValuesParser::new(Format::EXPORT_NAMES))
+                        .help("Format to write")
+                )
+                .arg(
+                    Arg::new("output")
+                        .short('o')
+                        .long("output")
+                        .help("File to write instead of printing to stdout")
+                )
+                .arg(
+                    Arg::new("where")
+                        .short('w')
+                        .long("where")
+                        .help("Only export tasks matching a filter, e.g. 'tag:sprint-4'")
+                )
+        )
+        .subcommand(
+            Command::new("scan")
+                .about("Turn TODO/FIXME/XXX comments of a source tree into tasks\nMore info: scan --help")
+                .arg(
+                    Arg::new("dir")
+                        .help("The directory to scan (.gitignore rules are respected)")
+                        .default_value(".")
+                        .index(1)
+                )
+                .arg(
+                    Arg::new("dry-run")
+                        .long("dry-run")
+                        .action(ArgAction::SetTrue)
+                        .help("Show what would change without saving")
+                )
+        )
+        .subcommand(
+            Command::new("sync")
+                .about("Version the task files with git and sync them with a remote\nMore info: sync --help")
+                .arg(
+                    Arg::new("init")
+                        .long("init")
+                        .action(ArgAction::SetTrue)
+                        .help("Turn the data directory into a git repository")
+                )
+                .arg(
+                    Arg::new("remote")
+                        .long("remote")
+                        .value_name("URL")
+                        .help("Set the git remote to pull from and push to")
+                )
+        )
+        .subcommand(
+            Command::new("merge")
+                .about("Three-way merge of tasks files, usable as a git merge driver\nMore info: merge --help")
+                .arg(Arg::new("base").help("The common ancestor (git's %O)").required(true).index(1))
+                .arg(Arg::new("ours").help("Our version, overwritten with the result (git's %A)").required(true).index(2))
+                .arg(Arg::new("theirs").help("Their version (git's %B)").required(true).index(3))
+                .arg(
+                    Arg::new("output")
+                        .short('o')
+                        .long("output")
+                        .help("Write the result here instead of over <ours>")
+                )
+                .arg(
+                    Arg::new("history")
+                        .long("history")
+                        .value_name("FILE")
+                        .help("History log whose task IDs count as issued, so renumbered tasks never reuse them")
+                )
+        )
+        .subcommand(
+            Command::new("note")
+                .about("Add a timestamped note to a task, or correct one\nMore info: note --help")
+                .arg(
+                    Arg::new("id")
+                        .help("The task: an ID, '#last', a UUID prefix or part of its title")
+                        .required(true)
+                        .index(1)
+                )
+                .arg(
+                    Arg::new("text")
+                        .help("The note (use - to read it from stdin)")
+                        .required_unless_present("delete")
+                        .index(2)
+                )
+                .arg(
+                    Arg::new("edit")
+                        .long("edit")
+                        .value_name("N")
+                        .value_parser(clap::value_parser!(usize))
+                        .conflicts_with("delete")
+                        .help("Replace the text of note N (as numbered by read)")
+                )
+                .arg(
+                    Arg::new("delete")
+                        .long("delete")
+                        .value_name("N")
+                        .value_parser(clap::value_parser!(usize))
+                        .conflicts_with("text")
+                        .help("Delete note N (as numbered by read)")
+                )
+        )
+        .subcommand(
+            Command::new("start")
+                .about("Start the timer on a task, stopping any other running timer")
+                .arg(
+                    Arg::new("id")
+                        .help("The task to work on: an ID, '#last', a UUID prefix or part of its title")
+                        .required(true)
+                        .index(1)
+                )
+        )
+        .subcommand(
+            Command::new("stop")
+                .about("Stop the running timer")
+        )
+        .subcommand(
+            Command::new("track")
+                .about("Record time worked on a task\nMore info: track --help")
+                .arg(
+                    Arg::new("id")
+                        .help("The task worked on: an ID, '#last', a UUID prefix or part of its title")
+                        .required(true)
+                        .index(1)
+                )
+                .arg(
+                    Arg::new("duration")
+                        .help("Time worked, e.g. 1h30m, 45m or 1.5h")
+                        .required(true)
+                        .index(2)
+                )
+                .arg(
+                    Arg::new("from")
+                        .long("from")
+                        .help("When the work started, e.g. 'yesterday 14:00' (default: it ends now)")
+                )
+        )
+        .subcommand(
+            Command::new("report")
+                .about("Summarise recorded data\nMore info: report --help")
+                .subcommand(
+                    Command::new("time")
+                        .about("Hours worked, from timers and tracked time")
+                        .arg(
+                            Arg::new("since")
+                                .long("since")
+                                .help("Only count time from this date on, e.g. monday, 2026-10-01 or '2 weeks ago'")
+                        )
+                        .arg(
+                            Arg::new("until")
+                                .long("until")
+                                .help("Only count time up to this date (included)")
+                        )
+                        .arg(
+                            Arg::new("by")
+                                .long("by")
+                                .default_value("task")
+                                .value_parser(PossibleValuesParser::new(["task", "tag", "project", "day"]))
+                                .help("Group the hours per task, tag, project or day")
+                        )
+                        .arg(
+                            Arg::new("where")
+                                .short('w')
+                                .long("where")
+                                .help("Only count tasks matching a filter, e.g. 'tag:client-x'")
+                        )
+                )
+        )
+        .subcommand(
+            Command::new("stats")
+                .about("Show counts, completion rate, throughput and breakdowns of the tasks\nMore info: stats --help")
+                .arg(
+                    Arg::new("where")
+                        .short('w')
+                        .long("where")
+                        .help("Only count tasks matching a filter, e.g. 'project:website'")
+                )
+                .arg(
+                    Arg::new("output")
+                        .long("output")
+                        .default_value("text")
+                        .value_parser(PossibleValuesParser::new(["text", "json"]))
+                        .help("Print a readable summary, or JSON for dashboards")
+                )
+        )
+        .subcommand(
+            Command::new("tui")
+                .about("Open the full-screen interactive interface")
+        )
+        .subcommand(
+            Command::new("serve")
+                .about("Serve the tasks over a local HTTP REST API\nMore info: serve --help")
+                .arg(
+                    Arg::new("port")
+                        .short('p')
+                        .long("port")
+                        .default_value("8080")
+                        .value_parser(clap::value_parser!(u16))
+                        .help("Port to listen on")
+                )
+                .arg(
+                    Arg::new("host")
+                        .long("host")
+                        .default_value("127.0.0.1")
+                        .help("Address to bind; use 0.0.0.0 to accept connections from other machines")
+                )
+        )
+        .subcommand(
+            Command::new("rpc")
+                .about("Speak JSON-RPC 2.0 on stdin/stdout for editor integrations")
+        )
+        .subcommand(
+            Command::new("history")
+                .about("Show every recorded change of a task")
+                .arg(
+                    Arg::new("id")
+                        .help("The ID of the task whose history to show")
+                        .required(true)
+                        .index(1)
+                )
+        )
+        .subcommand(
+            Command::new("log")
+                .about("Show the audit log of all changes\nMore info: log --help")
+                .arg(
+                    Arg::new("since")
+                        .long("since")
+                        .required(false)
+                        .help("Only show changes made on or after this date, e.g. 2026-10-01, yesterday or '2 weeks ago'")
+                )
+        )
+}
+
+/// Adds the arguments shared by subcommands that act on several tasks at once:
+/// positional IDs, ranges or task references, a '--where' filter, '--dry-run'
+/// and '--yes'.
+fn with_ids(command: Command, ids_help: &'static str) -> Command {
+    command
+        .arg(
+            Arg::new("ids")
+                .help(ids_help)
+                .long_help(format!(
+                    "{} (e.g. 3 5 7-12), '#last', '#prev', a UUID prefix (e.g. 1b4e28ba) or part of a title",
+                    ids_help
+                ))
+                .num_args(1..)
+                .required_unless_present("where")
+                .index(1)
+        )
+        .arg(
+            Arg::new("where")
+                .short('w')
+                .long("where")
+                .help("Select tasks matching a filter, e.g. 'tag:sprint-4 done:no'")
+        )
+}
+
+fn with_selection(command: Command, ids_help: &'static str) -> Command {
+    with_ids(command, ids_help)
+        .arg(
+            Arg::new("dry-run")
+                .long("dry-run")
+                .action(ArgAction::SetTrue)
+                .help("Show what would change without saving")
+        )
+        .arg(
+            Arg::new("yes")
+                .short('y')
+                .long("yes")
+                .action(ArgAction::SetTrue)
+                .help("Skip the confirmation prompt for large selections")
         )
-}
\ No newline at end of file
+}
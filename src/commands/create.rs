@@ -2,38 +2,168 @@
 // This file handles the "create" subcommand for our ToDo application.
 //
 // 1) The 'handle_create' function uses 'clap' (ArgMatches) to read the user's
-//    input: the 'title' and 'description' of the new task.
+//    input: the 'title' and optional 'description' of the new task, plus optional
+//    tags, '--due' / '--scheduled' dates ("tomorrow 14:00", "next friday",
+//    "in 3 days", ...) and an '--estimate' ("2h" or "3pt"). A description of "-" is read from stdin, so multi-line
+//    text can be piped or typed in (finish with Ctrl-D).
 //
-// 2) It then calls 'create_task' on the provided 'task_manager' (from models/TaskManager),
-//    which updates the in-memory task list and saves the new data to JSON.
+// 2) With '--from-file <path>' (or "-" for stdin) several tasks are created at
+//    once: one per Markdown checklist item ("- [ ] ..." / "- [x] ...") if the
+//    file has any, otherwise one per non-empty line. Tags, dates and the
+//    estimate apply to all of them.
 //
-// 3) Finally, it prints information about the newly created task or logs any error.
+// 3) It then calls 'create_task' / 'create_tasks' on the provided 'task_manager'
+//    (from models/TaskManager), which updates the in-memory task list and saves
+//    the new data to JSON.
+//
+// 4) Finally, it prints information about the newly created tasks or logs any error.
 //
 // Communication with Other Files:
 // - main.rs: Invokes 'handle_create' when the user enters the "create" subcommand.
 // - models/TaskManager: The 'create_task' method is defined here, handling the actual
 //   creation logic and file I/O.
-// - cli/app.rs: Defines the subcommand structure and required arguments (title, description).
+// - models/dates.rs: 'parse_human_date' reads the '--due' and '--scheduled' dates.
+// - cli/app.rs: Defines the subcommand structure and arguments (title, description,
+//   from-file, tag, due, scheduled, estimate).
 ////////////////////////////////////////////////////////////////////////////////
 
+use std::fs;
+use std::io::{self, Read};
 use clap::ArgMatches;
-use crate::models::TaskManager;
+use crate::formats::display_timestamp;
+use crate::models::{Estimate, Task, TaskManager};
+use crate::models::dates::parse_human_date;
 
 pub fn handle_create(args: &ArgMatches, task_manager: &mut TaskManager) -> Result<(), Box<dyn std::error::Error>> {
-    
+    let tags: Vec<&String> = args.get_many::<String>("tag").map(|v| v.collect()).unwrap_or_default();
+    let date_arg = |name: &str| args.get_one::<String>(name).map(|v| parse_human_date(v)).transpose();
+    let estimate = args.get_one::<String>("estimate").map(|v| estimate_arg(v)).transpose();
+    let (due, scheduled, estimate) = match (date_arg("due"), date_arg("scheduled"), estimate) {
+        (Ok(due), Ok(scheduled), Ok(estimate)) => (due, scheduled, estimate),
+        (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => {
+            println!("Error: {}", e);
+            return Ok(());
+        }
+    };
+    // What every created task gets on top of its title.
+    let extra = Task { due, scheduled, estimate, ..Task::default() };
+
+    if let Some(path) = args.get_one::<String>("from-file") {
+        return create_from_file(path, &tags, &extra, task_manager);
+    }
+
     let title = args.get_one::<String>("title").expect("Required").to_string();
-    let description = args.get_one::<String>("description").expect("Required").to_string();
+    let description = match args.get_one::<String>("description").map(String::as_str) {
+        Some("-") => read_stdin()?.trim_end().to_string(),
+        Some(description) => description.to_string(),
+        None => String::new(),
+    };
 
+    let mut task = Task { title, description, ..extra };
+    for tag in &tags {
+        task.add_tag(tag);
+    }
 
-    match task_manager.create_task(title, description) {
+    match task_manager.create_task(task) {
         // if create_task returns Ok(task), print the task details
         Ok(task) => {
             println!("\nTask created successfully!");
             println!("ID: {}", task.id);
             println!("Title: {}", task.title);
             println!("Description: {}", task.description);
+            if !task.tags.is_empty() {
+                println!("Tags: {}", task.tags.join(", "));
+            }
+            if let Some(due) = task.due {
+                println!("Due: {}", display_timestamp(due));
+            }
+            if let Some(scheduled) = task.scheduled {
+                println!("Scheduled: {}", display_timestamp(scheduled));
+            }
+            if let Some(estimate) = task.estimate {
+                println!("Estimate: {}", String::from(estimate));
+            }
         }
         Err(e) => println!("Failed to create task: {}", e),
     }
     Ok(())
-}
\ No newline at end of file
+}
+
+fn create_from_file(
+    path: &str,
+    tags: &[&String],
+    extra: &Task,
+    task_manager: &mut TaskManager,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let text = if path == "-" { read_stdin()? } else { fs::read_to_string(path)? };
+
+    let mut tasks = parse_task_lines(&text);
+    if tasks.is_empty() {
+        println!("No tasks found in {}", path);
+        return Ok(());
+    }
+    for task in &mut tasks {
+        task.due = extra.due;
+        task.scheduled = extra.scheduled;
+        task.estimate = extra.estimate;
+        for tag in tags {
+            task.add_tag(tag);
+        }
+    }
+
+    match task_manager.create_tasks(tasks) {
+        Ok(created) => {
+            println!("\n{} task(s) created successfully!", created.len());
+            for task in created {
+                let mark = if task.done { "x" } else { " " };
+                println!("{} - [{}] {}", task.id, mark, task.title);
+            }
+        }
+        Err(e) => println!("Failed to create tasks: {}", e),
+    }
+    Ok(())
+}
+
+/// Turns text into tasks: Markdown checklist items when present, otherwise one task
+/// per non-empty line. Checked items ("- [x]") are created as done.
+fn parse_task_lines(text: &str) -> Vec<Task> {
+    let checklist: Vec<Task> = text.lines().filter_map(parse_checklist_item).collect();
+    if !checklist.is_empty() {
+        return checklist;
+    }
+
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| Task { title: line.to_string(), ..Task::default() })
+        .collect()
+}
+
+fn parse_checklist_item(line: &str) -> Option<Task> {
+    let item = line.trim_start();
+    let item = item.strip_prefix("- ").or_else(|| item.strip_prefix("* "))?;
+    let (done, title) = if let Some(rest) = item.strip_prefix("[ ]") {
+        (false, rest)
+    } else if let Some(rest) = item.strip_prefix("[x]").or_else(|| item.strip_prefix("[X]")) {
+        (true, rest)
+    } else {
+        return None;
+    };
+
+    let title = title.trim();
+    if title.is_empty() {
+        return None;
+    }
+    Some(Task { title: title.to_string(), done, ..Task::default() })
+}
+
+/// Parses an `--estimate` value such as "2h" or "3pt".
+pub fn estimate_arg(value: &str) -> Result<Estimate, Box<dyn std::error::Error>> {
+    Ok(Estimate::try_from(value.to_string())?)
+}
+
+fn read_stdin() -> Result<String, Box<dyn std::error::Error>> {
+    let mut text = String::new();
+    io::stdin().read_to_string(&mut text)?;
+    Ok(text)
+}
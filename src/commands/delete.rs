@@ -2,21 +2,25 @@
 // This file handles the "delete" subcommand for our ToDo application.
 //
 // 1) The 'handle_delete' function uses 'clap' (ArgMatches) to read the user's 
-//    input, extracting the 'id' parameter which identifies the task to be deleted.
+//    input: one or more IDs or ranges (e.g. "delete 3 5 7-12") and/or a
+//    '--where' filter identifying the tasks to be deleted.
 //
-// 2) The function then attempts to parse the provided 'id' as a positive integer. 
-//    If the parse fails, an error message is printed, and the program exits.
+// 2) The selection is resolved by commands/selection.rs. If an ID is not a
+//    positive number or a valid range, an error message is printed.
 //
-// 3) We then call 'delete_task' on the given 'task_manager' (from models::TaskManager).
-//    If successful, this removes the task from the in-memory list and updates
-//    the JSON file. Otherwise, it returns an error.
+// 3) With '--dry-run' we only list the tasks that would be removed. Otherwise,
+//    above the confirmation threshold we ask before continuing.
 //
-// 4) Finally, the function prints a success or error message, then returns a 
+// 4) We then call 'delete_tasks' on the given 'task_manager' (from models::TaskManager).
+//    If successful, this removes all selected tasks from the in-memory list and
+//    updates the JSON file once. If any ID is unknown, nothing is removed.
+//
+// 5) Finally, the function prints a success or error message, then returns a 
 //    Result indicating success or failure.
 //
 // Communication with Other Files:
 // - main.rs: Invokes 'handle_delete' when the user enters the "delete" subcommand.
-// - models/TaskManager: The 'delete_task' method is defined here, managing the list
+// - models/TaskManager: The 'delete_tasks' method is defined here, managing the list
 //   of tasks and performing the file I/O to save changes.
 // - cli/app.rs: The 'delete' subcommand is defined, specifying required arguments.
 //
@@ -24,23 +28,45 @@
 
 use clap::ArgMatches;
 use crate::models::TaskManager;
+use super::selection::{confirm, select_ids};
 
 pub fn handle_delete(args: &ArgMatches, task_manager: &mut TaskManager) -> Result<(), Box<dyn std::error::Error>> {
+    let ids = match select_ids(args, task_manager) {
+        Ok(ids) => ids,
+        Err(e) => {
+            println!("Error: {}", e);
+            return Ok(());
+        }
+    };
+    if ids.is_empty() {
+        println!("No tasks matched");
+        return Ok(());
+    }
 
-    let id = args.get_one::<String>("id")
-        .expect("ID is required")
-        .parse::<u32>()
-        .unwrap_or_else(|_| {
-            println!("Error: ID must be a positive number");
-            std::process::exit(1);
-        });
-        
-    println!("Deleting task {}...", id);
+    if args.get_flag("dry-run") {
+        println!("\nDry run: {} task(s) would be deleted", ids.len());
+        for id in &ids {
+            match task_manager.read_task(*id) {
+                Ok(task) => println!("{} - {}", task.id, task.title),
+                Err(_) => println!("{} - (not found)", id),
+            }
+        }
+        return Ok(());
+    }
+
+    if !confirm(args, "delete", ids.len()) {
+        println!("Aborted");
+        return Ok(());
+    }
 
-    match task_manager.delete_task(id) {
-        Ok(_) => println!("Task {} deleted successfully", id),
+    match task_manager.delete_tasks(&ids) {
+        Ok(removed) => {
+            for task in removed {
+                println!("Task {} deleted successfully", task.id);
+            }
+        }
         Err(e) => println!("Error: {}", e),
     }
 
     Ok(())
-}
\ No newline at end of file
+}
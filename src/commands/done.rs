@@ -0,0 +1,71 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file handles the "done" and "reopen" subcommands for our ToDo application.
+//
+// 1) Both handlers resolve the selected task IDs (IDs, ranges and/or a '--where'
+//    filter such as "tag:sprint-4") via commands/selection.rs.
+//
+// 2) 'handle_done' marks every selected task as completed; 'handle_reopen'
+//    marks them as open again. Both share 'set_done'.
+//
+// 3) '--dry-run' previews the change, and selections above the confirmation
+//    threshold ask before continuing unless '--yes' is given.
+//
+// 4) The change is applied with 'modify_tasks', so the store is loaded and
+//    saved exactly once for the whole selection.
+//
+// Communication with Other Files:
+// - main.rs: Invokes these handlers for the "done" and "reopen" subcommands.
+// - models/TaskManager: 'modify_tasks' and 'preview_modify' perform the update.
+// - cli/app.rs: Defines both subcommands with the shared selection arguments.
+////////////////////////////////////////////////////////////////////////////////
+
+use clap::ArgMatches;
+use crate::models::TaskManager;
+use super::selection::{confirm, print_preview, select_ids};
+
+pub fn handle_done(args: &ArgMatches, task_manager: &mut TaskManager) -> Result<(), Box<dyn std::error::Error>> {
+    set_done(args, task_manager, true)
+}
+
+pub fn handle_reopen(args: &ArgMatches, task_manager: &mut TaskManager) -> Result<(), Box<dyn std::error::Error>> {
+    set_done(args, task_manager, false)
+}
+
+fn set_done(args: &ArgMatches, task_manager: &mut TaskManager, done: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let ids = match select_ids(args, task_manager) {
+        Ok(ids) => ids,
+        Err(e) => {
+            println!("Error: {}", e);
+            return Ok(());
+        }
+    };
+    if ids.is_empty() {
+        println!("No tasks matched");
+        return Ok(());
+    }
+
+    if args.get_flag("dry-run") {
+        match task_manager.preview_modify(&ids, |task| task.done = done) {
+            Ok(changes) => print_preview(&changes),
+            Err(e) => println!("Error: {}", e),
+        }
+        return Ok(());
+    }
+
+    let action = if done { "complete" } else { "reopen" };
+    if !confirm(args, action, ids.len()) {
+        println!("Aborted");
+        return Ok(());
+    }
+
+    match task_manager.modify_tasks(&ids, |task| task.done = done) {
+        Ok(tasks) => {
+            let state = if done { "done" } else { "open" };
+            for task in tasks {
+                println!("Task {} marked as {}", task.id, state);
+            }
+        }
+        Err(e) => println!("Error: {}", e),
+    }
+    Ok(())
+}
@@ -0,0 +1,221 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file handles the "edit" subcommand for our ToDo application.
+//
+// 1) The 'handle_edit' function reads the 'id' argument and loads the task with
+//    'task_manager.read_task(id)'.
+//
+// 2) The task is written to a temporary Markdown file, private to the user and
+//    removed afterwards even if the editor fails: every field except the
+//    description goes into a YAML-style front matter block between '---' lines,
+//    and the description becomes the document body, so multi-line text is easy
+//    to write.
+//
+// 3) The file is opened in $VISUAL (or $EDITOR, falling back to 'vi'). Once the
+//    editor exits, the document is parsed and validated back into a Task. If it
+//    is invalid the user may re-open the editor to fix it.
+//
+// 4) Finally, 'task_manager.update_task' stores the result, which also records
+//    the change in the audit log.
+//
+// Communication with Other Files:
+// - main.rs: Invokes 'handle_edit' when the user enters the "edit" subcommand.
+// - models/TaskManager: 'read_task' and 'update_task' load and store the task.
+// - cli/app.rs: Defines the "edit" subcommand and its 'id' argument.
+////////////////////////////////////////////////////////////////////////////////
+
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+use std::process::Command;
+use clap::ArgMatches;
+use serde_json::{Map, Value};
+use uuid::Uuid;
+use crate::models::{Task, TaskManager};
+
+pub fn handle_edit(args: &ArgMatches, task_manager: &mut TaskManager) -> Result<(), Box<dyn std::error::Error>> {
+    let id = match args.get_one::<String>("id").expect("ID is required").parse::<u32>() {
+        Ok(id) => id,
+        Err(_) => {
+            println!("Error: ID must be a positive number");
+            return Ok(());
+        }
+    };
+
+    let task = match task_manager.read_task(id) {
+        Ok(task) => task,
+        Err(e) => {
+            println!("Error: {}", e);
+            return Ok(());
+        }
+    };
+
+    let edited = match edit_in_editor(&task)? {
+        Some(edited) => edited,
+        None => {
+            println!("Edit cancelled, task {} unchanged", id);
+            return Ok(());
+        }
+    };
+
+    match task_manager.update_task(id, edited) {
+        Ok(task) => println!("Task updated successfully: {:?}", task),
+        Err(e) => println!("Error updating task: {}", e),
+    }
+    Ok(())
+}
+
+/// Opens the task in the user's editor until it parses, returning the edited task.
+/// Returns `None` when the user gives up after an invalid document.
+pub fn edit_in_editor(task: &Task) -> Result<Option<Task>, Box<dyn std::error::Error>> {
+    let file = TempDocument::create(task.id, &to_document(task)?)?;
+
+    loop {
+        run_editor(&file.path)?;
+        let text = fs::read_to_string(&file.path)?;
+        match from_document(&text, task) {
+            Ok(edited) => return Ok(Some(edited)),
+            Err(e) => {
+                println!("Error: {}", e);
+                if !ask("Re-open the editor to fix it? [Y/n] ") {
+                    return Ok(None);
+                }
+            }
+        }
+    }
+}
+
+/// The temporary file a task is edited in. It gets a random name, is created
+/// only if no such file exists and is readable by the user alone (on Unix), and
+/// is removed when dropped, whether editing succeeded or failed.
+struct TempDocument {
+    path: PathBuf,
+}
+
+impl TempDocument {
+    fn create(id: u32, contents: &str) -> io::Result<TempDocument> {
+        let path = std::env::temp_dir().join(format!("todo-{}-{}.md", id, Uuid::new_v4().simple()));
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = options.open(&path)?;
+        let document = TempDocument { path };
+        file.write_all(contents.as_bytes())?;
+        Ok(document)
+    }
+}
+
+impl Drop for TempDocument {
+    fn drop(&mut self) {
+        fs::remove_file(&self.path).ok();
+    }
+}
+
+/// Launches $VISUAL/$EDITOR on the file and waits for it to exit.
+fn run_editor(path: &std::path::Path) -> Result<(), Box<dyn std::error::Error>> {
+    let editor = ["VISUAL", "EDITOR"]
+        .iter()
+        .find_map(|key| std::env::var(key).ok().filter(|v| !v.trim().is_empty()))
+        .unwrap_or_else(|| "vi".to_string());
+
+    // Editors are often configured with arguments, e.g. "code --wait".
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().ok_or("No editor configured")?;
+    let status = Command::new(program).args(parts).arg(path).status()
+        .map_err(|e| format!("Could not start editor '{}': {}", editor, e))?;
+
+    if !status.success() {
+        return Err(format!("Editor '{}' exited with {}", editor, status).into());
+    }
+    Ok(())
+}
+
+fn ask(question: &str) -> bool {
+    print!("{}", question);
+    io::stdout().flush().ok();
+    let mut answer = String::new();
+    if io::stdin().lock().read_line(&mut answer).unwrap_or(0) == 0 {
+        return false;
+    }
+    !matches!(answer.trim().to_lowercase().as_str(), "n" | "no")
+}
+
+/// Serializes a task as front matter (every field but the description) followed by
+/// the description as the document body. Strings are written as-is; other values
+/// use their JSON form, which is also valid YAML.
+pub fn to_document(task: &Task) -> Result<String, Box<dyn std::error::Error>> {
+    let mut doc = String::from("---\n");
+    for (key, value) in task_fields(task)? {
+        if key == "description" {
+            continue;
+        }
+        let rendered = match &value {
+            Value::String(s) if s.trim() == s && !s.contains('\n') && !s.starts_with('"') => s.clone(),
+            other => other.to_string(),
+        };
+        doc.push_str(&format!("{}: {}\n", key, rendered));
+    }
+    doc.push_str("---\n");
+    doc.push_str(&task.description);
+    doc.push('\n');
+    Ok(doc)
+}
+
+/// Parses a document produced by `to_document` back into a task, validating that
+/// the ID and UUID are unchanged, the title is not empty and every field has a valid value.
+pub fn from_document(text: &str, original: &Task) -> Result<Task, Box<dyn std::error::Error>> {
+    let rest = text.trim_start().strip_prefix("---")
+        .ok_or("The document must start with a '---' front matter line")?;
+    let (front, body) = rest.split_once("\n---")
+        .ok_or("The front matter must be closed with a '---' line")?;
+    // Drop the remainder of the closing '---' line.
+    let body = body.split_once('\n').map(|(_, b)| b).unwrap_or("");
+
+    let original_fields = task_fields(original)?;
+    let mut fields = original_fields.clone();
+
+    for line in front.lines().filter(|l| !l.trim().is_empty()) {
+        let (key, raw) = line.split_once(':')
+            .ok_or_else(|| format!("Expected 'field: value', got '{}'", line))?;
+        let key = key.trim();
+        let raw = raw.trim();
+        let value = match original_fields.get(key) {
+            None => return Err(format!("Unknown field '{}'", key).into()),
+            // Strings are taken literally unless the user quoted them.
+            Some(Value::String(_)) => match serde_json::from_str::<Value>(raw) {
+                Ok(Value::String(s)) if raw.starts_with('"') => Value::String(s),
+                _ => Value::String(raw.to_string()),
+            },
+            // Empty optional fields accept either JSON or plain text.
+            Some(Value::Null) => serde_json::from_str(raw).unwrap_or_else(|_| Value::String(raw.to_string())),
+            Some(_) => serde_json::from_str(raw)
+                .map_err(|_| format!("Invalid value for '{}': {}", key, raw))?,
+        };
+        fields.insert(key.to_string(), value);
+    }
+    fields.insert("description".to_string(), Value::String(body.trim_end().to_string()));
+
+    let task: Task = serde_json::from_value(Value::Object(fields))
+        .map_err(|e| format!("Invalid task: {}", e))?;
+
+    if task.id != original.id {
+        return Err("The id field cannot be changed".into());
+    }
+    if task.uuid != original.uuid {
+        return Err("The uuid field cannot be changed".into());
+    }
+    if task.title.trim().is_empty() {
+        return Err("The title cannot be empty".into());
+    }
+    Ok(task)
+}
+
+fn task_fields(task: &Task) -> Result<Map<String, Value>, Box<dyn std::error::Error>> {
+    match serde_json::to_value(task)? {
+        Value::Object(map) => Ok(map),
+        _ => Err("Task did not serialize to an object".into()),
+    }
+}
@@ -0,0 +1,59 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file handles the "export" subcommand for our ToDo application.
+//
+// 1) The 'handle_export' function reads the '--format', an optional '--where'
+//    filter and an optional '--output' file from ArgMatches.
+//
+// 2) The selected tasks are rendered by the matching 'formats' submodule and
+//    written to the output file, or to stdout when none is given so the result
+//    can be piped elsewhere.
+//
+// Communication with Other Files:
+// - main.rs: Invokes 'handle_export' when the user enters the "export" subcommand.
+// - formats/: Renders tasks in each supported file format.
+// - models/filter.rs: 'TaskFilter' narrows down the exported tasks.
+// - cli/app.rs: Declares the "export" subcommand and its options.
+////////////////////////////////////////////////////////////////////////////////
+
+use std::fs;
+use clap::ArgMatches;
+use crate::formats::{self, Format};
+use crate::models::{Task, TaskFilter, TaskManager};
+
+pub fn handle_export(args: &ArgMatches, task_manager: &TaskManager) -> Result<(), Box<dyn std::error::Error>> {
+    let format = Format::from_name(args.get_one::<String>("format").expect("has default"))
+        .expect("validated by clap");
+
+    let filter = match args.get_one::<String>("where").map(|w| TaskFilter::parse(w)) {
+        Some(Ok(filter)) => Some(filter),
+        Some(Err(e)) => {
+            println!("Error: {}", e);
+            return Ok(());
+        }
+        None => None,
+    };
+    let tasks: Vec<Task> = task_manager
+        .tasks
+        .iter()
+        .filter(|t| filter.as_ref().is_none_or(|f| f.matches(t)))
+        .cloned()
+        .collect();
+
+    let text = match format {
+        Format::TodoTxt => formats::todotxt::render(&tasks),
+        Format::ICal => formats::ical::render(&tasks),
+        Format::Markdown => formats::markdown::render(&tasks),
+        Format::Taskwarrior => formats::taskwarrior::render(&tasks)?,
+        Format::Org => formats::org::render(&tasks),
+        Format::Csv => unreachable!("CSV export is not offered by the CLI"),
+    };
+
+    match args.get_one::<String>("output") {
+        Some(path) => {
+            fs::write(path, text)?;
+            println!("Exported {} task(s) to {}", tasks.len(), path);
+        }
+        None => print!("{}", text),
+    }
+    Ok(())
+}
@@ -0,0 +1,59 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file handles the "history" subcommand for our ToDo application.
+//
+// 1) The 'handle_history' function reads the 'id' argument from ArgMatches and
+//    parses it as a positive number, exactly like the "read" subcommand.
+//
+// 2) It asks the 'task_manager' for every audit log entry recorded for that ID.
+//    This works for deleted tasks too, since the log is never rewritten.
+//
+// 3) Each entry is printed with its timestamp, author, kind of change and the
+//    old → new value of every field that changed.
+//
+// Communication with Other Files:
+// - main.rs: Invokes 'handle_history' when the user enters the "history" subcommand.
+// - models/TaskManager: 'task_history' reads the entries from the audit log.
+// - models/history.rs: Defines 'ChangeEntry', the record printed here.
+// - commands/log.rs: Reuses 'print_entry' to display the global log.
+////////////////////////////////////////////////////////////////////////////////
+
+use chrono::Local;
+use clap::ArgMatches;
+use crate::models::{ChangeEntry, TaskManager};
+
+pub fn handle_history(args: &ArgMatches, task_manager: &TaskManager) -> Result<(), Box<dyn std::error::Error>> {
+    let id = match args.get_one::<String>("id").expect("ID is required").parse::<u32>() {
+        Ok(id) => id,
+        Err(_) => {
+            println!("Error: ID must be a positive number");
+            return Ok(());
+        }
+    };
+
+    let entries = task_manager.task_history(id)?;
+    if entries.is_empty() {
+        println!("No history recorded for task {}", id);
+        return Ok(());
+    }
+
+    println!("\n=== History of task {} ===", id);
+    for entry in &entries {
+        print_entry(entry);
+    }
+    Ok(())
+}
+
+/// Prints a single audit log entry followed by one line per changed field.
+pub fn print_entry(entry: &ChangeEntry) {
+    println!("{:-<50}", "");
+    println!(
+        "{}  task {}  {} by {}",
+        entry.timestamp.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S"),
+        entry.task_id,
+        entry.kind,
+        entry.author,
+    );
+    for change in &entry.changes {
+        println!("    {}: {} → {}", change.field, change.old, change.new);
+    }
+}
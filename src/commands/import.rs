@@ -0,0 +1,191 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file handles the "import" subcommand for our ToDo application.
+//
+// 1) The 'handle_import' function reads the '--format' and the input file (or
+//    "-" for stdin), then lets the matching 'formats' submodule turn the text
+//    into draft tasks. For CSV, '--map title=Summary,...' chooses the columns.
+//
+// 2) Drafts carrying the ID of an existing task (e.g. a todo.txt file that was
+//    exported, edited and imported back) update that task; the format only
+//    changes the fields it carries (see 'formats::overlay'). Other drafts whose
+//    title matches an existing task, or an earlier draft of the same file, are
+//    reported as duplicates and skipped (case and surrounding spaces are ignored).
+//    Subtasks of a skipped draft are attached to the task it duplicates.
+//
+// 3) With '--dry-run' we only print what would be imported or changed. Otherwise
+//    new tasks go through 'create_tasks', which assigns fresh IDs with the same
+//    logic as "create", then updates through 'modify_tasks'; each saves once.
+//
+// Communication with Other Files:
+// - main.rs: Invokes 'handle_import' when the user enters the "import" subcommand.
+// - formats/: Parses each supported file format into tasks.
+// - models/TaskManager: 'create_tasks' and 'modify_tasks' store the result.
+// - cli/app.rs: Declares the "import" subcommand and its options.
+////////////////////////////////////////////////////////////////////////////////
+
+use std::collections::hash_map::{Entry, HashMap};
+use std::fs;
+use std::io::{self, Read};
+use clap::ArgMatches;
+use crate::formats::{self, Format};
+use crate::models::{ChangeEntry, Task, TaskManager};
+use super::selection::print_preview;
+
+pub fn handle_import(args: &ArgMatches, task_manager: &mut TaskManager) -> Result<(), Box<dyn std::error::Error>> {
+    let format = Format::from_name(args.get_one::<String>("format").expect("has default"))
+        .expect("validated by clap");
+    let path = args.get_one::<String>("file").expect("File is required");
+
+    let text = if path == "-" {
+        let mut text = String::new();
+        io::stdin().read_to_string(&mut text)?;
+        text
+    } else {
+        fs::read_to_string(path)?
+    };
+
+    let parsed = match parse(format, &text, args, &task_manager.tasks) {
+        Ok(tasks) => tasks,
+        Err(e) => {
+            println!("Error: {}", e);
+            return Ok(());
+        }
+    };
+
+    // Drafts for known IDs are updates; the others are new tasks unless their title exists.
+    let (updates, drafts): (Vec<Task>, Vec<Task>) = parsed
+        .into_iter()
+        .partition(|t| t.id != 0 && task_manager.tasks.iter().any(|e| e.id == t.id));
+    let (mut tasks, duplicates, merged) = split_duplicates(drafts, &task_manager.tasks);
+    for task in &duplicates {
+        println!("Skipping duplicate: {}", task.title);
+    }
+    tasks.iter_mut().for_each(|t| relink(t, &merged));
+
+    // Keep only the updates that actually change something. The formats already
+    // laid them over the stored task, so fields a file cannot carry (UUID,
+    // tracked time, estimate, ...) are unchanged.
+    let mut updates: HashMap<u32, Task> = updates
+        .into_iter()
+        .filter_map(|mut draft| {
+            let stored = task_manager.tasks.iter().find(|t| t.id == draft.id)?;
+            relink(&mut draft, &merged);
+            ChangeEntry::between(Some(stored), Some(&draft)).is_some().then_some(draft)
+        })
+        .map(|t| (t.id, t))
+        .collect();
+    let mut update_ids: Vec<u32> = updates.keys().copied().collect();
+    update_ids.sort_unstable();
+
+    if tasks.is_empty() && update_ids.is_empty() {
+        println!("Nothing to import");
+        return Ok(());
+    }
+
+    if args.get_flag("dry-run") {
+        println!(
+            "\nDry run: {} task(s) would be imported, {} updated, {} duplicate(s) skipped",
+            tasks.len(), update_ids.len(), duplicates.len()
+        );
+        for task in &tasks {
+            print_task(task, true);
+        }
+        if !update_ids.is_empty() {
+            print_preview(&task_manager.preview_modify(&update_ids, |task| apply(task, &updates))?);
+        }
+        return Ok(());
+    }
+
+    // New tasks go first, so updates can point at the IDs they receive.
+    if !tasks.is_empty() {
+        let provisional: Vec<u32> = tasks.iter().map(|t| t.id).collect();
+        match task_manager.create_tasks(tasks) {
+            Ok(created) => {
+                println!("\n{} task(s) imported, {} duplicate(s) skipped", created.len(), duplicates.len());
+                for task in &created {
+                    print_task(task, false);
+                }
+                let assigned: HashMap<u32, u32> = provisional
+                    .into_iter()
+                    .zip(created.iter().map(|t| t.id))
+                    .filter(|(old, _)| *old != 0)
+                    .collect();
+                updates.values_mut().for_each(|t| relink(t, &assigned));
+            }
+            Err(e) => {
+                println!("Failed to import tasks: {}", e);
+                return Ok(());
+            }
+        }
+    }
+    if !update_ids.is_empty() {
+        match task_manager.modify_tasks(&update_ids, |task| apply(task, &updates)) {
+            Ok(updated) => println!("\n{} task(s) updated", updated.len()),
+            Err(e) => println!("Failed to update tasks: {}", e),
+        }
+    }
+    Ok(())
+}
+
+fn parse(format: Format, text: &str, args: &ArgMatches, existing: &[Task]) -> Result<Vec<Task>, Box<dyn std::error::Error>> {
+    match format {
+        Format::Csv => {
+            let map = match args.get_one::<String>("map") {
+                Some(spec) => formats::csv::ColumnMap::parse(spec)?,
+                None => formats::csv::ColumnMap::default(),
+            };
+            formats::csv::parse(text, &map)
+        }
+        Format::TodoTxt => formats::todotxt::parse(text, existing),
+        Format::ICal => formats::ical::parse(text, existing),
+        Format::Markdown => formats::markdown::parse(text, existing),
+        Format::Taskwarrior => formats::taskwarrior::parse(text, existing),
+        Format::Org => formats::org::parse(text, existing),
+    }
+}
+
+/// Separates drafts whose title already exists (in the store or earlier in the file).
+/// Also returns, for each skipped draft with a provisional ID, the ID of the task it
+/// duplicates, so its subtasks can be attached there instead.
+fn split_duplicates(parsed: Vec<Task>, existing: &[Task]) -> (Vec<Task>, Vec<Task>, HashMap<u32, u32>) {
+    let normalize = |title: &str| title.trim().to_lowercase();
+    let mut seen: HashMap<String, u32> = existing.iter().map(|t| (normalize(&t.title), t.id)).collect();
+    let mut merged = HashMap::new();
+
+    let (tasks, duplicates) = parsed.into_iter().partition(|task| match seen.entry(normalize(&task.title)) {
+        Entry::Vacant(entry) => {
+            entry.insert(task.id);
+            true
+        }
+        Entry::Occupied(entry) => {
+            if task.id != 0 {
+                merged.insert(task.id, *entry.get());
+            }
+            false
+        }
+    });
+    (tasks, duplicates, merged)
+}
+
+/// Points `parent` at the task an ID was replaced by, if it was.
+fn relink(task: &mut Task, ids: &HashMap<u32, u32>) {
+    if let Some(&parent) = task.parent.as_ref().and_then(|p| ids.get(p)) {
+        task.parent = Some(parent);
+    }
+}
+
+fn apply(task: &mut Task, updates: &HashMap<u32, Task>) {
+    if let Some(draft) = updates.get(&task.id) {
+        *task = draft.clone();
+    }
+}
+
+fn print_task(task: &Task, new: bool) {
+    let mark = if task.done { "x" } else { " " };
+    let id = if new { "new".to_string() } else { task.id.to_string() };
+    if task.tags.is_empty() {
+        println!("{} - [{}] {}", id, mark, task.title);
+    } else {
+        println!("{} - [{}] {}  #{}", id, mark, task.title, task.tags.join(" #"));
+    }
+}
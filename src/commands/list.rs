@@ -5,10 +5,15 @@
 //    line tool with the "list" subcommand. 
 //    Example: `cargo run list`
 //
-// 2) It does not require any additional arguments or flags.
+// 2) An optional '--where' filter narrows the list, using the same syntax as
+//    the bulk subcommands, including date bounds such as 'due.before:eow'.
 //
-// 3) The function simply calls 'task_manager.list_tasks()', which prints out
-//    all existing tasks and a summary. 
+// 3) The function then calls 'task_manager.list_tasks()', which prints out
+//    the matching tasks (with their due date and estimate) and a summary.
+//
+// 4) With '--sum estimate' a footer adds up the estimates of the listed tasks:
+//    durations and story points, the time tracked against duration estimates
+//    and the effort remaining on open tasks, for sprint capacity planning.
 //    
 // Communication with Other Files:
 // - main.rs: Matches the "list" subcommand and delegates to this function.
@@ -16,6 +21,7 @@
 //   information about tasks in memory.
 // - cli/app.rs: Declares the "list" subcommand structure, linking user input
 //   to this function in a clean, modular way.
+// - models/filter.rs: 'TaskFilter' parses and evaluates the '--where' expression.
 //
 // Behind the Scenes:
 // - The 'list_tasks()' method in TaskManager accesses its internal Vec<Task>
@@ -24,12 +30,55 @@
 //
 // Usage Example:
 //   cargo run list   // Displays all tasks dynamically from data/todos.json
+//   cargo run list --where "done:no due.before:next-friday"
+//   cargo run list --where "tag:sprint-5" --sum estimate
 ////////////////////////////////////////////////////////////////////////////////
 
 
-use crate::models::TaskManager;
+use chrono::{Duration, Utc};
+use clap::ArgMatches;
+use crate::models::{Effort, TaskFilter, TaskManager};
+use crate::models::dates::{format_duration, now};
+
+pub fn handle_list(args: &ArgMatches, task_manager: &TaskManager) -> Result<(), Box<dyn std::error::Error>> {
+    let filter = match args.get_one::<String>("where").map(|w| TaskFilter::parse(w)) {
+        Some(Ok(filter)) => filter,
+        Some(Err(e)) => {
+            println!("Error: {}", e);
+            return Ok(());
+        }
+        None => TaskFilter::default(),
+    };
+    task_manager.list_tasks(&filter);
 
-pub fn handle_list(task_manager: &TaskManager) -> Result<(), Box<dyn std::error::Error>> {
-    task_manager.list_tasks();
+    if args.get_one::<String>("sum").is_some() {
+        let effort = if args.contains_id("where") {
+            let listed = task_manager.tasks.iter().filter(|t| filter.matches(t));
+            Effort::of(listed, now().with_timezone(&Utc))
+        } else {
+            task_manager.get_stats().effort
+        };
+        print_effort(&effort);
+    }
     Ok(())
+}
+
+fn print_effort(effort: &Effort) {
+    let has_duration = effort.estimated > Duration::zero();
+    let mut estimate = Vec::new();
+    if has_duration || effort.points == 0.0 {
+        estimate.push(format_duration(effort.estimated));
+    }
+    if effort.points > 0.0 {
+        estimate.push(format!("{}pt", effort.points));
+    }
+    println!("Estimate: {}", estimate.join(" + "));
+
+    if has_duration {
+        if effort.tracked > Duration::zero() {
+            let share = effort.tracked.num_minutes() * 100 / effort.estimated.num_minutes().max(1);
+            println!("Tracked: {} ({}% of the estimate)", format_duration(effort.tracked), share);
+        }
+        println!("Remaining: {}", format_duration(effort.remaining));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,47 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file handles the "log" subcommand for our ToDo application.
+//
+// 1) The 'handle_log' function reads the optional '--since' argument, a date
+//    such as "2026-10-01", "yesterday" or "2 weeks ago", or a full RFC 3339
+//    timestamp (see models/dates.rs).
+//
+// 2) It loads the whole audit log from the 'task_manager' and keeps only the
+//    entries recorded at or after that point in time (or all of them).
+//
+// 3) Entries are printed oldest first using the same layout as "history".
+//
+// Communication with Other Files:
+// - main.rs: Invokes 'handle_log' when the user enters the "log" subcommand.
+// - models/TaskManager: 'history' returns every entry in the audit log.
+// - commands/history.rs: Provides 'print_entry' for consistent output.
+// - models/dates.rs: 'parse_past_date' reads the '--since' date.
+////////////////////////////////////////////////////////////////////////////////
+
+use clap::ArgMatches;
+use crate::models::TaskManager;
+use crate::models::dates::parse_past_date;
+use super::history::print_entry;
+
+pub fn handle_log(args: &ArgMatches, task_manager: &TaskManager) -> Result<(), Box<dyn std::error::Error>> {
+    let since = match args.get_one::<String>("since").map(|value| parse_past_date(value)) {
+        Some(Ok(since)) => Some(since),
+        Some(Err(e)) => {
+            println!("Error: {}", e);
+            return Ok(());
+        }
+        None => None,
+    };
+
+    let entries: Vec<_> = task_manager
+        .history()?
+        .into_iter()
+        .filter(|e| since.is_none_or(|since| e.timestamp >= since))
+        .collect();
+
+    println!("\n=== Change Log ===");
+    for entry in &entries {
+        print_entry(entry);
+    }
+    println!("\nTotal Changes: {}", entries.len());
+    Ok(())
+}
@@ -0,0 +1,67 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file handles the "merge" subcommand for our ToDo application.
+//
+// 1) 'handle_merge' reads three versions of a tasks JSON file: their common
+//    ancestor (base), ours and theirs. It merges them task by task and field by
+//    field with 'merge_tasks' (see models/merge.rs). The result overwrites "ours",
+//    or goes to '--output'.
+//
+// 2) This is exactly what git expects from a merge driver, so git can merge the
+//    tasks file instead of doing a line-based merge of the pretty JSON:
+//      .gitattributes:  todos.json merge=todo
+//      git config merge.todo.driver "todo-rust-cli merge %O %A %B --history todos.history.jsonl"
+//    'sync --init' sets this up for the data directory. '--history' names the
+//    history log, whose task IDs (deleted tasks included) are never handed out
+//    again when a task is renumbered.
+//
+// 3) Renumbered tasks and genuine conflicts are reported. Conflicts make the
+//    command exit with status 1 so git marks the file as conflicted. The written
+//    file is still valid, holding the local value of each conflicting field.
+//
+// Communication with Other Files:
+// - main.rs: Invokes 'handle_merge' before loading the task store, since git runs
+//   merge drivers from the repository root where there is no data/ directory.
+// - models/merge.rs: The three-way merge itself.
+// - cli/app.rs: Declares the "merge" subcommand and its arguments.
+////////////////////////////////////////////////////////////////////////////////
+
+use std::fs;
+use std::path::Path;
+use clap::ArgMatches;
+use crate::models::merge::{last_issued_in, merge_tasks};
+use crate::models::Task;
+
+/// Merges the files and returns whether the merge was free of conflicts.
+pub fn handle_merge(args: &ArgMatches) -> Result<bool, Box<dyn std::error::Error>> {
+    let file = |name: &str| args.get_one::<String>(name).expect("required by clap");
+    let (base, ours, theirs) = (read_tasks(file("base"))?, read_tasks(file("ours"))?, read_tasks(file("theirs"))?);
+    let output = args.get_one::<String>("output").unwrap_or(file("ours"));
+
+    let last_issued = match args.get_one::<String>("history") {
+        Some(path) if Path::new(path).exists() => last_issued_in(&fs::read_to_string(path)?),
+        _ => 0,
+    };
+
+    let outcome = merge_tasks(&base, &ours, &theirs, last_issued);
+    fs::write(output, serde_json::to_string_pretty(&outcome.tasks)?)?;
+
+    for (old, new) in &outcome.renumbered {
+        println!("Task {} was created on both sides; theirs is now task {}", old, new);
+    }
+    for conflict in &outcome.conflicts {
+        println!("Conflict: {}", conflict);
+    }
+    Ok(outcome.conflicts.is_empty())
+}
+
+/// Reads a tasks file; a missing or empty file (e.g. no common ancestor) is an empty list.
+fn read_tasks(path: &str) -> Result<Vec<Task>, Box<dyn std::error::Error>> {
+    if !Path::new(path).exists() {
+        return Ok(Vec::new());
+    }
+    let text = fs::read_to_string(path)?;
+    if text.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    serde_json::from_str(&text).map_err(|e| format!("{}: {}", path, e).into())
+}
@@ -2,21 +2,27 @@
 // This file is the entry point for the 'commands' module.
 //
 // Explanation:
-// 1) We declare submodules for each subcommand: create, read, update, delete, list.
+// 1) We declare submodules for each subcommand: create, read, update, delete, list,
+//    history, log, edit, note, import, export, scan, sync, merge, tui, shell, serve, rpc, done (which also handles "reopen"),
+//    timer (the "start", "stop" and "track" subcommands), report and stats. The 'selection' submodule
+//    holds the helpers shared by subcommands acting on several tasks at once.
 //    These submodules hold the logic for the corresponding CLI operations.
 //
 // 2) We then re-export specific functions (e.g., handle_create, handle_read) so
 //    other parts of the application (like main.rs) can invoke them directly via
 //    commands::handle_create or commands::handle_read, etc.
 //
-// 3) This centralizes and organizes all command-oriented functions in one place,
+// 3) 'dispatch' maps a parsed subcommand to its handler, so main.rs and the
+//    interactive shell share one routing table.
+//
+// 4) This centralizes and organizes all command-oriented functions in one place,
 //    ensuring each subcommand has a dedicated file for clarity, while this 
 //    mod.rs file provides a single point of access.
 //
 // Communication with Other Files:
 // - main.rs: When the user inputs a subcommand (e.g., "create", "read", etc.),
-//   main.rs matches this subcommand and then calls the appropriate function
-//   re-exported here (e.g., handle_create).
+//   main.rs passes the parsed input to 'dispatch', which calls the appropriate
+//   function re-exported here (e.g., handle_create).
 // - create.rs, read.rs, update.rs, delete.rs, list.rs: Each file implements the
 //   logic necessary to process its respective subcommand, such as reading project
 //   arguments, updating data, and printing results.
@@ -39,9 +45,82 @@ pub mod read;
 pub mod update;
 pub mod delete;
 pub mod list;
+pub mod history;
+pub mod log;
+pub mod done;
+pub mod edit;
+pub mod note;
+pub mod tui;
+pub mod shell;
+pub mod serve;
+pub mod rpc;
+pub mod import;
+pub mod export;
+pub mod scan;
+pub mod sync;
+pub mod merge;
+pub mod timer;
+pub mod report;
+pub mod stats;
+pub mod selection;
 
 pub use create::handle_create;
 pub use read::handle_read;
 pub use update::handle_update;
 pub use delete::handle_delete;
-pub use list::handle_list;
\ No newline at end of file
+pub use list::handle_list;
+pub use history::handle_history;
+pub use log::handle_log;
+pub use done::{handle_done, handle_reopen};
+pub use edit::handle_edit;
+pub use note::handle_note;
+pub use tui::handle_tui;
+pub use shell::handle_shell;
+pub use serve::handle_serve;
+pub use rpc::handle_rpc;
+pub use import::handle_import;
+pub use export::handle_export;
+pub use scan::handle_scan;
+pub use sync::handle_sync;
+pub use merge::handle_merge;
+pub use timer::{handle_start, handle_stop, handle_track};
+pub use report::handle_report;
+pub use stats::handle_stats;
+
+use clap::ArgMatches;
+use crate::models::TaskManager;
+
+/// Runs the handler matching the parsed subcommand. Used by main.rs for a single
+/// command and by the "shell" subcommand for every line typed.
+pub fn dispatch(matches: &ArgMatches, task_manager: &mut TaskManager) -> Result<(), Box<dyn std::error::Error>> {
+    match matches.subcommand() {
+        Some(("create", args)) => handle_create(args, task_manager)?,
+        Some(("read", args)) => handle_read(args, task_manager)?,
+        Some(("update", args)) => handle_update(args, task_manager)?,
+        Some(("delete", args)) => handle_delete(args, task_manager)?,
+        Some(("done", args)) => handle_done(args, task_manager)?,
+        Some(("reopen", args)) => handle_reopen(args, task_manager)?,
+        Some(("edit", args)) => handle_edit(args, task_manager)?,
+        Some(("note", args)) => handle_note(args, task_manager)?,
+        Some(("list", sub_m)) => handle_list(sub_m, task_manager)?,
+        Some(("history", args)) => handle_history(args, task_manager)?,
+        Some(("log", args)) => handle_log(args, task_manager)?,
+        Some(("tui", _)) => handle_tui(task_manager)?,
+        Some(("serve", args)) => handle_serve(args, task_manager)?,
+        Some(("rpc", _)) => handle_rpc(task_manager)?,
+        Some(("import", args)) => handle_import(args, task_manager)?,
+        Some(("export", args)) => handle_export(args, task_manager)?,
+        Some(("scan", args)) => handle_scan(args, task_manager)?,
+        Some(("sync", args)) => handle_sync(args, task_manager)?,
+        Some(("start", args)) => handle_start(args, task_manager)?,
+        Some(("stop", _)) => handle_stop(task_manager)?,
+        Some(("track", args)) => handle_track(args, task_manager)?,
+        Some(("report", args)) => handle_report(args, task_manager)?,
+        Some(("stats", args)) => handle_stats(args, task_manager)?,
+        Some(("merge", args)) => {
+            handle_merge(args)?;
+        }
+        _ => println!("No subcommand was used. Use --help for usage information."),
+    }
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,90 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file handles the "note" subcommand for our ToDo application.
+//
+// 1) 'handle_note' appends a timestamped note to one task (an ID or any
+//    reference such as '#last' or part of a title), e.g.
+//    "note 3 'Reproduced on staging'". Unlike 'update -d', the description and
+//    earlier notes are left alone, so notes build up a running log. A text of
+//    "-" is read from stdin.
+//
+// 2) Notes are numbered from 1 in the order "read" lists them. '--edit N'
+//    replaces the text of note N (keeping its timestamp) and '--delete N'
+//    removes it.
+//
+// 3) The change is saved through 'modify_tasks', so it is recorded in the
+//    history like any other update.
+//
+// Communication with Other Files:
+// - main.rs: Invokes 'handle_note' when the user enters the "note" subcommand.
+// - models/Task: 'notes' holds the log of 'Note's.
+// - models/TaskManager: 'modify_tasks' stores the change.
+// - models/dates.rs: 'now' (which honours TODO_NOW) stamps new notes.
+// - commands/selection.rs: 'select_one' resolves the task reference.
+// - cli/app.rs: Declares the "note" subcommand and its options.
+////////////////////////////////////////////////////////////////////////////////
+
+use std::io::{self, Read};
+use chrono::Utc;
+use clap::ArgMatches;
+use crate::models::{Note, TaskManager};
+use crate::models::dates::now;
+use super::selection::select_one;
+
+pub fn handle_note(args: &ArgMatches, task_manager: &mut TaskManager) -> Result<(), Box<dyn std::error::Error>> {
+    let id = match select_one(args.get_one::<String>("id").expect("Required"), task_manager) {
+        Ok(id) => id,
+        Err(e) => {
+            println!("Error: {}", e);
+            return Ok(());
+        }
+    };
+    let text = match args.get_one::<String>("text").map(String::as_str) {
+        Some("-") => Some(read_stdin()?.trim_end().to_string()),
+        Some(text) => Some(text.to_string()),
+        None => None,
+    };
+    if text.as_deref().is_some_and(|t| t.trim().is_empty()) {
+        println!("Error: The note is empty");
+        return Ok(());
+    }
+
+    let edit = args.get_one::<usize>("edit").copied();
+    let delete = args.get_one::<usize>("delete").copied();
+    if let Some(number) = edit.or(delete) {
+        let count = task_manager.read_task(id)?.notes.len();
+        if number == 0 || number > count {
+            println!("Error: Task {} has no note {} (it has {})", id, number, count);
+            return Ok(());
+        }
+    }
+
+    let entry = now().with_timezone(&Utc);
+    let result = task_manager.modify_tasks(&[id], |task| match (edit, delete, &text) {
+        (Some(number), _, Some(text)) => task.notes[number - 1].text = text.clone(),
+        (_, Some(number), _) => {
+            task.notes.remove(number - 1);
+        }
+        (_, _, Some(text)) => task.notes.push(Note { entry, text: text.clone() }),
+        _ => {}
+    });
+    match result {
+        Ok(tasks) => {
+            for task in tasks {
+                let action = match (edit, delete) {
+                    (Some(number), _) => format!("Edited note {} of", number),
+                    (_, Some(number)) => format!("Deleted note {} of", number),
+                    _ => format!("Added note {} to", task.notes.len()),
+                };
+                println!("{} task {} - {}", action, task.id, task.title);
+            }
+        }
+        Err(e) => println!("Error: {}", e),
+    }
+    Ok(())
+}
+
+fn read_stdin() -> Result<String, Box<dyn std::error::Error>> {
+    let mut text = String::new();
+    io::stdin().read_to_string(&mut text)?;
+    Ok(text)
+}
@@ -2,45 +2,117 @@
 // This file handles the "read" subcommand for our ToDo application.
 //
 // Explanation:
-// 1) The `handle_read` function retrieves the 'id' argument from ArgMatches, 
-//    converting it into a `u32`.
+// 1) The `handle_read` function resolves the task IDs from ArgMatches: one or more
+//    IDs or ranges (e.g. "read 3 5 7-12") and/or a `--where` filter.
 // 2) We then use the `task_manager.read_task(id)` method (from the `models` module) 
-//    to look for a matching task.
-// 3) On success, it prints out the task details (ID, title, description). 
+//    to look up each matching task.
+// 3) On success, it prints out the task details (ID, title, status, priority,
+//    project, parent task and subtasks, tags, dates, source, estimate, time
+//    worked, description, notes); optional fields are shown only when set.
 //    On failure, it displays an error.
-// 4) Any error during conversion or lookup causes a message to be printed 
-//    and the application to exit.
+// 4) Any error during conversion or lookup causes a message to be printed.
 //
 // Communication with Other Files:
 // - main.rs: Invokes `handle_read` when the user chooses the "read" subcommand.
 // - models::TaskManager: The actual logic for looking up a task is in `read_task`.
-// - cli/app.rs: Sets up the "read" subcommand and `id` parameter in the CLI structure.
+// - commands/selection.rs: Turns IDs, ranges and filters into a list of task IDs.
+// - cli/app.rs: Sets up the "read" subcommand and `ids` parameter in the CLI structure.
 //    That allows main.rs to forward the command (and any arguments) to this function.
 //
 // Generally:
 // - The flow is: user calls "read <id>" → main.rs → handle_read → TaskManager → prints task info.
 ////////////////////////////////////////////////////////////////////////////////
 
+use chrono::Utc;
 use clap::ArgMatches;
+use crate::formats::display_timestamp;
 use crate::models::TaskManager;
+use crate::models::dates::{format_duration, now};
+use super::selection::select_ids;
 
 pub fn handle_read(args: &ArgMatches, task_manager: &TaskManager) -> Result<(), Box<dyn std::error::Error>> {
-    let id = args.get_one::<String>("id")
-        .expect("ID is required")
-        .parse::<u32>()
-        .unwrap_or_else(|_| {
-            println!("Error: ID must be a positive number");
-            std::process::exit(1);
-        });
+    let ids = match select_ids(args, task_manager) {
+        Ok(ids) => ids,
+        Err(e) => {
+            println!("Error: {}", e);
+            return Ok(());
+        }
+    };
+    if ids.is_empty() {
+        println!("No tasks matched");
+    }
 
-    match task_manager.read_task(id) {
-        Ok(task) => {
-            println!("\n|| ===== Task details ===== ||");
-            println!("ID: {}", task.id);
-            println!("Title: {}", task.title);
-            println!("Description:\n {}\n", task.description);
-        },
-        Err(e) => println!("Error: {}", e),
+    for id in ids {
+        match task_manager.read_task(id) {
+            Ok(task) => {
+                println!("\n|| ===== Task details ===== ||");
+                println!("ID: {}", task.id);
+                println!("UUID: {}", task.uuid);
+                println!("Title: {}", task.title);
+                println!("Status: {}", if task.done { "done" } else { "open" });
+                if let Some(priority) = task.priority {
+                    println!("Priority: {}", priority);
+                }
+                if let Some(project) = &task.project {
+                    println!("Project: {}", project);
+                }
+                if let Some(parent) = task.parent {
+                    match task_manager.read_task(parent) {
+                        Ok(parent) => println!("Parent: {} - {}", parent.id, parent.title),
+                        Err(_) => println!("Parent: {} (deleted)", parent),
+                    }
+                }
+                let subtasks: Vec<String> = task_manager
+                    .tasks
+                    .iter()
+                    .filter(|t| t.parent == Some(task.id))
+                    .map(|t| t.id.to_string())
+                    .collect();
+                if !subtasks.is_empty() {
+                    println!("Subtasks: {}", subtasks.join(", "));
+                }
+                if !task.tags.is_empty() {
+                    println!("Tags: {}", task.tags.join(", "));
+                }
+                if let Some(due) = task.due {
+                    println!("Due: {}", display_timestamp(due));
+                }
+                if let Some(scheduled) = task.scheduled {
+                    println!("Scheduled: {}", display_timestamp(scheduled));
+                }
+                if let Some(source) = &task.source {
+                    println!("Source: {}", source);
+                }
+                if let Some(rule) = &task.recurrence {
+                    println!("Repeats: {}", rule);
+                }
+                if let Some(created) = task.created {
+                    println!("Created: {}", display_timestamp(created));
+                }
+                if let Some(completed) = task.completed {
+                    println!("Completed: {}", display_timestamp(completed));
+                }
+                if let Some(estimate) = task.estimate {
+                    println!("Estimate: {}", String::from(estimate));
+                }
+                if !task.time_entries.is_empty() {
+                    let tracked = format_duration(task.time_tracked(now().with_timezone(&Utc)));
+                    match task.running_since() {
+                        Some(since) => println!("Time: {} (running since {})", tracked, display_timestamp(since)),
+                        None => println!("Time: {}", tracked),
+                    }
+                }
+                println!("Description:\n {}\n", task.description);
+                if !task.notes.is_empty() {
+                    println!("Notes:");
+                    for (number, note) in task.notes.iter().enumerate() {
+                        println!(" {}. [{}] {}", number + 1, display_timestamp(note.entry), note.text);
+                    }
+                    println!();
+                }
+            },
+            Err(e) => println!("Error: {}", e),
+        }
     }
     Ok(())
-}
\ No newline at end of file
+}
@@ -0,0 +1,107 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file handles the "report" subcommand for our ToDo application.
+//
+// 1) "report time" sums the time recorded by "start"/"stop" and "track". With
+//    '--since' / '--until' (e.g. "--since monday") only the part of each entry
+//    inside that window counts; a running timer counts up to now.
+//
+// 2) '--by' groups the hours per task (the default), tag, project or day (the
+//    day an entry started). With '--by tag' a task carrying several tags counts
+//    for each of them, so the total line sums every entry once instead of the
+//    groups. '--where' limits the report to matching tasks.
+//
+// 3) Durations are shown as "1h30m" and as decimal hours for billing.
+//
+// Communication with Other Files:
+// - main.rs: Invokes 'handle_report' when the user enters the "report" subcommand.
+// - models/Task: 'time_entries' holds the recorded time, 'TimeEntry::within'
+//   clips an entry to the reported window.
+// - models/dates.rs: 'now' and 'parse_past_date' read the window.
+// - cli/app.rs: Declares "report" and its "time" report with their options.
+////////////////////////////////////////////////////////////////////////////////
+
+use chrono::{Duration, Utc};
+use clap::ArgMatches;
+use crate::formats::{display_timestamp, local_date};
+use crate::models::{Task, TaskFilter, TaskManager, TimeEntry};
+use crate::models::dates::{end_of_day, format_duration, now, parse_past_date};
+
+pub fn handle_report(args: &ArgMatches, task_manager: &TaskManager) -> Result<(), Box<dyn std::error::Error>> {
+    match args.subcommand() {
+        Some(("time", args)) => report_time(args, task_manager),
+        _ => {
+            println!("Choose a report, e.g. 'report time'. Use --help for details.");
+            Ok(())
+        }
+    }
+}
+
+fn report_time(args: &ArgMatches, task_manager: &TaskManager) -> Result<(), Box<dyn std::error::Error>> {
+    let since = args.get_one::<String>("since").map(|v| parse_past_date(v)).transpose();
+    let until = args.get_one::<String>("until").map(|v| parse_past_date(v).map(end_of_day)).transpose();
+    let filter = args.get_one::<String>("where").map(|w| TaskFilter::parse(w)).transpose();
+    let (since, until, filter) = match (since, until, filter) {
+        (Ok(since), Ok(until), Ok(filter)) => (since, until, filter.unwrap_or_default()),
+        (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => {
+            println!("Error: {}", e);
+            return Ok(());
+        }
+    };
+    let now = now().with_timezone(&Utc);
+    let until = until.map_or(now, |until| until.min(now));
+    let by = args.get_one::<String>("by").map(String::as_str).unwrap_or("task");
+
+    let mut groups: Vec<(String, Duration)> = Vec::new();
+    let mut total = Duration::zero();
+    for task in task_manager.tasks.iter().filter(|t| filter.matches(t)) {
+        for entry in &task.time_entries {
+            let worked = entry.within(since, until);
+            if worked <= Duration::zero() {
+                continue;
+            }
+            total += worked;
+            for key in group_keys(by, task, entry) {
+                match groups.iter_mut().find(|(k, _)| *k == key) {
+                    Some((_, sum)) => *sum += worked,
+                    None => groups.push((key, worked)),
+                }
+            }
+        }
+    }
+    // Tasks keep their ID order; names and days read best sorted.
+    if by != "task" {
+        groups.sort_by(|a, b| a.0.cmp(&b.0));
+    }
+
+    let window = match since {
+        Some(since) => format!(" since {}", display_timestamp(since)),
+        None => String::new(),
+    };
+    println!("\n=== Time by {}{} ===", by, window);
+    if groups.is_empty() {
+        println!("No time recorded");
+        return Ok(());
+    }
+    let width = groups.iter().map(|(k, _)| k.chars().count()).max().unwrap_or(0).max(5);
+    for (key, worked) in &groups {
+        println!("{:<width$}  {:>8}  {:>7.2}h", key, format_duration(*worked), hours(*worked), width = width);
+    }
+    println!("{:-<1$}", "", width + 21);
+    println!("{:<width$}  {:>8}  {:>7.2}h", "Total", format_duration(total), hours(total), width = width);
+    Ok(())
+}
+
+/// The groups an entry's time counts for.
+fn group_keys(by: &str, task: &Task, entry: &TimeEntry) -> Vec<String> {
+    match by {
+        "tag" if task.tags.is_empty() => vec!["(no tag)".to_string()],
+        "tag" => task.tags.clone(),
+        "project" => vec![task.project.clone().unwrap_or_else(|| "(no project)".to_string())],
+        "day" => vec![local_date(entry.start).format("%Y-%m-%d %a").to_string()],
+        _ => vec![format!("{} - {}", task.id, task.title)],
+    }
+}
+
+fn hours(duration: Duration) -> f64 {
+    duration.num_minutes() as f64 / 60.0
+}
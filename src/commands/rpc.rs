@@ -0,0 +1,22 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file handles the "rpc" subcommand for our ToDo application.
+//
+// 1) The 'handle_rpc' function hands the loaded 'task_manager' to 'rpc::run',
+//    which speaks JSON-RPC 2.0 over stdin/stdout until stdin is closed.
+//
+// 2) Editor plugins keep this process running and send requests such as
+//    {"jsonrpc":"2.0","id":1,"method":"tasks/list"} instead of spawning the CLI
+//    for every action.
+//
+// Communication with Other Files:
+// - main.rs: Invokes 'handle_rpc' when the user enters the "rpc" subcommand.
+// - rpc/: Implements message framing, the methods and change notifications.
+// - cli/app.rs: Declares the "rpc" subcommand.
+////////////////////////////////////////////////////////////////////////////////
+
+use crate::models::TaskManager;
+use crate::rpc;
+
+pub fn handle_rpc(task_manager: &mut TaskManager) -> Result<(), Box<dyn std::error::Error>> {
+    rpc::run(task_manager)
+}
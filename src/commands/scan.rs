@@ -0,0 +1,208 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file handles the "scan" subcommand for our ToDo application.
+//
+// 1) The 'handle_scan' function walks a source tree with the 'ignore' crate, so
+//    .gitignore (and .ignore) rules and hidden files are skipped like git does.
+//    Every line holding a 'TODO:', 'FIXME:' or 'XXX:' comment (also the
+//    'TODO(name):' form) is collected, with the text after the marker as title.
+//
+// 2) Found comments are matched against tasks created by earlier scans, through
+//    their 'source' field ("path:line"): same file and same text is the same
+//    comment, so a comment that merely moved only updates the line number.
+//    Unmatched comments become new tasks tagged with their marker (e.g. "fixme").
+//
+// 3) Scanned tasks of files in the scanned directory whose comment was not found
+//    again are marked done; a task whose comment came back is reopened.
+//
+// 4) With '--dry-run' we only print what would change. Otherwise new tasks go
+//    through 'create_tasks' and changes through 'modify_tasks'; each saves once.
+//
+// Communication with Other Files:
+// - main.rs: Invokes 'handle_scan' when the user enters the "scan" subcommand.
+// - models/TaskManager: 'create_tasks' and 'modify_tasks' store the result.
+// - commands/selection.rs: 'print_preview' shows the changes of a dry run.
+// - cli/app.rs: Declares the "scan" subcommand and its options.
+////////////////////////////////////////////////////////////////////////////////
+
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use clap::ArgMatches;
+use ignore::WalkBuilder;
+use crate::models::{Task, TaskManager};
+use super::selection::print_preview;
+
+const MARKERS: [&str; 3] = ["TODO", "FIXME", "XXX"];
+const COMMENT_LEADERS: [&str; 6] = ["//", "#", "/*", "--", ";", "<!--"];
+
+/// A marker comment found in the source tree.
+struct Comment {
+    path: String,
+    line: usize,
+    marker: &'static str,
+    text: String,
+}
+
+pub fn handle_scan(args: &ArgMatches, task_manager: &mut TaskManager) -> Result<(), Box<dyn std::error::Error>> {
+    let dir = args.get_one::<String>("dir").expect("has default");
+    let (root, comments) = match scan(Path::new(dir)) {
+        Ok(result) => result,
+        Err(e) => {
+            println!("Error: cannot scan '{}': {}", dir, e);
+            return Ok(());
+        }
+    };
+
+    // Tasks from earlier scans of this tree; each comment claims the first match.
+    let mut candidates: Vec<&Task> = task_manager
+        .tasks
+        .iter()
+        .filter(|t| t.source.as_deref().and_then(source_path).is_some_and(|p| Path::new(p).starts_with(&root)))
+        .collect();
+    let mut new_tasks = Vec::new();
+    let mut changes: HashMap<u32, Task> = HashMap::new();
+    let (mut moved, mut reopened) = (0, 0);
+
+    for comment in comments {
+        let reference = format!("{}:{}", comment.path, comment.line);
+        let found = candidates.iter().position(|t| {
+            t.title == comment.text && t.source.as_deref().and_then(source_path) == Some(comment.path.as_str())
+        });
+        match found.map(|i| candidates.remove(i)) {
+            Some(task) if task.done || task.source.as_deref() != Some(reference.as_str()) => {
+                if task.done {
+                    reopened += 1;
+                } else {
+                    moved += 1;
+                }
+                changes.insert(task.id, Task { done: false, source: Some(reference), ..task.clone() });
+            }
+            Some(_) => {}
+            None => new_tasks.push(Task {
+                title: comment.text,
+                tags: vec![comment.marker.to_lowercase()],
+                source: Some(reference),
+                ..Task::default()
+            }),
+        }
+    }
+
+    // Whatever is left lost its comment.
+    let mut resolved = 0;
+    for task in candidates.into_iter().filter(|t| !t.done) {
+        resolved += 1;
+        changes.insert(task.id, Task { done: true, ..task.clone() });
+    }
+
+    let mut ids: Vec<u32> = changes.keys().copied().collect();
+    ids.sort_unstable();
+    let apply = |task: &mut Task| {
+        if let Some(changed) = changes.get(&task.id) {
+            *task = changed.clone();
+        }
+    };
+    let summary = format!(
+        "{} new, {} moved, {} reopened, {} resolved",
+        new_tasks.len(), moved, reopened, resolved
+    );
+
+    if args.get_flag("dry-run") {
+        println!("\nDry run: {}", summary);
+        for task in &new_tasks {
+            println!("new - {}  ({})", task.title, task.source.as_deref().unwrap_or_default());
+        }
+        if !ids.is_empty() {
+            print_preview(&task_manager.preview_modify(&ids, apply)?);
+        }
+        return Ok(());
+    }
+
+    if !new_tasks.is_empty() {
+        match task_manager.create_tasks(new_tasks) {
+            Ok(created) => {
+                for task in &created {
+                    println!("{} - {}  ({})", task.id, task.title, task.source.as_deref().unwrap_or_default());
+                }
+            }
+            Err(e) => {
+                println!("Failed to create tasks: {}", e);
+                return Ok(());
+            }
+        }
+    }
+    if !ids.is_empty() {
+        if let Err(e) = task_manager.modify_tasks(&ids, apply) {
+            println!("Failed to update tasks: {}", e);
+            return Ok(());
+        }
+    }
+    println!("\nScan finished: {}", summary);
+    Ok(())
+}
+
+/// Walks `dir` and returns its path as stored in task sources (relative to the
+/// current directory when inside it, so an empty path stands for ".") together
+/// with every marker comment, in file and line order.
+fn scan(dir: &Path) -> Result<(PathBuf, Vec<Comment>), Box<dyn std::error::Error>> {
+    let cwd = env::current_dir()?.canonicalize()?;
+    let relative = |path: &Path| path.strip_prefix(&cwd).map(Path::to_path_buf).unwrap_or_else(|_| path.to_path_buf());
+    let root = dir.canonicalize()?;
+
+    let mut files: Vec<PathBuf> = WalkBuilder::new(&root)
+        .build()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_some_and(|t| t.is_file()))
+        .map(|entry| entry.into_path())
+        .collect();
+    files.sort();
+
+    let mut comments = Vec::new();
+    for file in files {
+        // Binary and non-UTF-8 files cannot hold comments we could read.
+        let Ok(text) = fs::read_to_string(&file) else {
+            continue;
+        };
+        let path = relative(&file).display().to_string();
+        for (index, line) in text.lines().enumerate() {
+            if let Some((marker, text)) = find_marker(line) {
+                comments.push(Comment { path: path.clone(), line: index + 1, marker, text });
+            }
+        }
+    }
+    Ok((relative(&root), comments))
+}
+
+/// Finds "MARKER:" or "MARKER(who):" inside a comment and returns the marker and
+/// the text after it (or the marker itself when nothing follows).
+fn find_marker(line: &str) -> Option<(&'static str, String)> {
+    for (start, _) in line.char_indices() {
+        let rest = &line[start..];
+        let Some(marker) = MARKERS.iter().find(|m| rest.starts_with(**m)) else {
+            continue;
+        };
+        let before = &line[..start];
+        if before.chars().next_back().is_some_and(|c| c.is_alphanumeric() || c == '_') {
+            continue;
+        }
+        let in_comment = COMMENT_LEADERS.iter().any(|l| before.contains(l)) || before.trim_start().starts_with('*');
+        let mut after = &rest[marker.len()..];
+        if let Some(owner) = after.strip_prefix('(') {
+            after = owner.split_once(')').map(|(_, a)| a).unwrap_or(owner);
+        }
+        let Some(text) = after.strip_prefix(':').filter(|_| in_comment) else {
+            continue;
+        };
+
+        let text = text.trim().trim_end_matches("*/").trim_end_matches("-->").trim();
+        let title = if text.is_empty() { marker.to_string() } else { text.to_string() };
+        return Some((marker, title));
+    }
+    None
+}
+
+/// The path part of a "path:line" source reference.
+fn source_path(source: &str) -> Option<&str> {
+    let (path, line) = source.rsplit_once(':')?;
+    line.parse::<usize>().ok().map(|_| path)
+}
@@ -0,0 +1,210 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file holds the helpers shared by every subcommand that acts on several
+// tasks at once (read, update, delete, done, reopen).
+//
+// 1) 'select_ids' turns the positional IDs and the optional '--where' filter
+//    into a sorted, de-duplicated list of task IDs. IDs may be single numbers
+//    ("3") or inclusive ranges ("7-12"); ranges silently skip IDs that no
+//    longer exist, while a single missing ID is reported as an error.
+//    Instead of an ID, a task can be named by '#last' (the newest task) or
+//    '#prev' (the one before it), by a unique prefix of its UUID, or by part of
+//    its title. When a title matches several tasks we ask which one was meant,
+//    or fail when nobody is there to answer (stdin is not a terminal).
+//
+// 2) 'print_preview' shows what a '--dry-run' would change, field by field,
+//    using the same diff as the audit log.
+//
+// 3) 'confirm' asks the user before touching more than CONFIRM_THRESHOLD tasks,
+//    unless '--yes' was passed.
+//
+// Communication with Other Files:
+// - cli/app.rs: 'with_selection' adds the ids/--where/--dry-run/--yes arguments
+//   read here.
+// - models/filter.rs: 'TaskFilter' parses and evaluates '--where' expressions.
+// - models/TaskManager: Provides the task list the selection is resolved against.
+////////////////////////////////////////////////////////////////////////////////
+
+use std::io::{self, BufRead, IsTerminal, Write};
+use clap::ArgMatches;
+use crate::models::{ChangeEntry, Task, TaskFilter, TaskManager};
+
+/// Bulk operations touching more tasks than this ask for confirmation first.
+pub const CONFIRM_THRESHOLD: usize = 5;
+
+/// Resolves the IDs and `--where` filter of a bulk subcommand into task IDs.
+pub fn select_ids(args: &ArgMatches, task_manager: &TaskManager) -> Result<Vec<u32>, Box<dyn std::error::Error>> {
+    let specs: Vec<&String> = args.get_many::<String>("ids").map(|v| v.collect()).unwrap_or_default();
+    let filter = args.get_one::<String>("where").map(|w| TaskFilter::parse(w)).transpose()?;
+
+    if specs.is_empty() && filter.is_none() {
+        return Err("Provide at least one task ID or a --where filter".into());
+    }
+
+    let mut ids = Vec::new();
+    for spec in specs {
+        ids.extend(parse_id_spec(spec, task_manager)?);
+    }
+    if let Some(filter) = filter {
+        ids.extend(task_manager.tasks.iter().filter(|t| filter.matches(t)).map(|t| t.id));
+    }
+
+    ids.sort_unstable();
+    ids.dedup();
+    Ok(ids)
+}
+
+/// Resolves the single task a subcommand such as "start" acts on: an ID or any
+/// reference `select_ids` accepts except ranges.
+pub fn select_one(spec: &str, task_manager: &TaskManager) -> Result<u32, Box<dyn std::error::Error>> {
+    let id = match spec.trim().parse::<u32>() {
+        Ok(id) => id,
+        Err(_) => resolve_reference(spec.trim(), &task_manager.tasks)?,
+    };
+    match task_manager.read_task(id) {
+        Ok(task) => Ok(task.id),
+        Err(_) => Err(format!("Task {} not found", id).into()),
+    }
+}
+
+/// Parses "N" into `[N]`, "A-B" into the existing task IDs between A and B, and
+/// any other reference into the ID of the task it names.
+fn parse_id_spec(spec: &str, task_manager: &TaskManager) -> Result<Vec<u32>, Box<dyn std::error::Error>> {
+    let spec = spec.trim();
+    if let Ok(id) = spec.parse::<u32>() {
+        return Ok(vec![id]);
+    }
+    if let Some((start, end)) = spec.split_once('-') {
+        if let (Ok(start), Ok(end)) = (start.trim().parse::<u32>(), end.trim().parse::<u32>()) {
+            if start > end {
+                return Err(format!("Invalid range '{}': expected a range like 7-12", spec).into());
+            }
+            return Ok(task_manager
+                .tasks
+                .iter()
+                .map(|t| t.id)
+                .filter(|id| (start..=end).contains(id))
+                .collect());
+        }
+    }
+    resolve_reference(spec, &task_manager.tasks).map(|id| vec![id])
+}
+
+/// Finds the task named by "#last", "#prev", a UUID prefix or a title.
+/// A UUID prefix needs at least 4 characters and a digit, so plain words are
+/// always looked up in titles.
+fn resolve_reference(spec: &str, tasks: &[Task]) -> Result<u32, Box<dyn std::error::Error>> {
+    let mut newest: Vec<u32> = tasks.iter().map(|t| t.id).collect();
+    newest.sort_unstable_by(|a, b| b.cmp(a));
+    match spec.to_lowercase().as_str() {
+        "#last" => return newest.first().copied().ok_or_else(|| "There are no tasks yet".into()),
+        "#prev" => return newest.get(1).copied().ok_or_else(|| "There is no task before the last one".into()),
+        _ => {}
+    }
+
+    let prefix = spec.to_lowercase();
+    let uuid_like = prefix.len() >= 4
+        && prefix.chars().all(|c| c.is_ascii_hexdigit() || c == '-')
+        && prefix.chars().any(|c| c.is_ascii_digit());
+    if uuid_like {
+        let found: Vec<&Task> = tasks
+            .iter()
+            .filter(|t| t.uuid.to_string().starts_with(&prefix) || t.uuid.simple().to_string().starts_with(&prefix))
+            .collect();
+        match found.as_slice() {
+            [task] => return Ok(task.id),
+            [] => {}
+            _ => return Err(format!("UUID prefix '{}' matches {} tasks; type more of it", spec, found.len()).into()),
+        }
+    }
+
+    match title_matches(spec, tasks).as_slice() {
+        [] => Err(format!("No task matches '{}'", spec).into()),
+        [task] => Ok(task.id),
+        candidates => choose(spec, candidates),
+    }
+}
+
+/// Tasks whose title matches `query`, case-insensitively, from the best kind of
+/// match found: the whole title, then a substring, then every word of the query,
+/// then its letters in order (so "by mlk" finds "Buy milk").
+fn title_matches<'a>(query: &str, tasks: &'a [Task]) -> Vec<&'a Task> {
+    let query = query.to_lowercase();
+    let words: Vec<&str> = query.split_whitespace().collect();
+    let in_order = |title: &str| {
+        let mut letters = title.chars();
+        query.chars().all(|q| letters.any(|c| c == q))
+    };
+    let kinds: [&dyn Fn(&str) -> bool; 4] = [
+        &|title| title == query,
+        &|title| title.contains(&query),
+        &|title| words.iter().all(|w| title.contains(w)),
+        &in_order,
+    ];
+
+    for matches in kinds {
+        let found: Vec<&Task> = tasks.iter().filter(|t| matches(&t.title.to_lowercase())).collect();
+        if !found.is_empty() {
+            return found;
+        }
+    }
+    Vec::new()
+}
+
+/// Asks which of several matching tasks was meant.
+fn choose(spec: &str, candidates: &[&Task]) -> Result<u32, Box<dyn std::error::Error>> {
+    if !io::stdin().is_terminal() {
+        let names: Vec<String> = candidates.iter().map(|t| format!("{} - {}", t.id, t.title)).collect();
+        return Err(format!("'{}' matches several tasks ({}); use an ID", spec, names.join(", ")).into());
+    }
+
+    println!("'{}' matches several tasks:", spec);
+    for (number, task) in candidates.iter().enumerate() {
+        let status = if task.done { " (done)" } else { "" };
+        println!("  {}) {} - {}{}", number + 1, task.id, task.title, status);
+    }
+    print!("Which one? [1-{}, empty to cancel] ", candidates.len());
+    io::stdout().flush().ok();
+
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer)?;
+    answer
+        .trim()
+        .parse::<usize>()
+        .ok()
+        .and_then(|n| n.checked_sub(1))
+        .and_then(|i| candidates.get(i))
+        .map(|task| task.id)
+        .ok_or_else(|| "No task chosen".into())
+}
+
+/// Prints the field-level changes a bulk operation would make.
+pub fn print_preview(changes: &[(Task, Task)]) {
+    println!("\nDry run: {} task(s) would change", changes.len());
+    for (before, after) in changes {
+        match ChangeEntry::between(Some(before), Some(after)) {
+            Some(entry) => {
+                println!("{} - {}", before.id, before.title);
+                for change in &entry.changes {
+                    println!("    {}: {} → {}", change.field, change.old, change.new);
+                }
+            }
+            None => println!("{} - {} (unchanged)", before.id, before.title),
+        }
+    }
+}
+
+/// Asks for confirmation when `count` exceeds the threshold; `--yes` skips the prompt.
+pub fn confirm(args: &ArgMatches, action: &str, count: usize) -> bool {
+    if count <= CONFIRM_THRESHOLD || args.get_flag("yes") {
+        return true;
+    }
+
+    print!("About to {} {} tasks. Continue? [y/N] ", action, count);
+    io::stdout().flush().ok();
+
+    let mut answer = String::new();
+    if io::stdin().lock().read_line(&mut answer).is_err() {
+        return false;
+    }
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
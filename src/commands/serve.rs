@@ -0,0 +1,24 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file handles the "serve" subcommand for our ToDo application.
+//
+// 1) The 'handle_serve' function reads the '--host' and '--port' arguments
+//    (default 127.0.0.1:8080) from ArgMatches.
+//
+// 2) It then hands the 'task_manager' to 'server::run', which exposes the tasks
+//    as a JSON REST API until the process is stopped with Ctrl-C.
+//
+// Communication with Other Files:
+// - main.rs: Invokes 'handle_serve' when the user enters the "serve" subcommand.
+// - server/: Implements the HTTP server, routing and ETag handling.
+// - cli/app.rs: Declares the "serve" subcommand and its options.
+////////////////////////////////////////////////////////////////////////////////
+
+use clap::ArgMatches;
+use crate::models::TaskManager;
+use crate::server;
+
+pub fn handle_serve(args: &ArgMatches, task_manager: &mut TaskManager) -> Result<(), Box<dyn std::error::Error>> {
+    let host = args.get_one::<String>("host").expect("has default");
+    let port = args.get_one::<u16>("port").expect("has default");
+    server::run(&format!("{}:{}", host, port), task_manager)
+}
@@ -0,0 +1,234 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file handles the "shell" subcommand for our ToDo application.
+//
+// 1) 'handle_shell' keeps the already loaded 'task_manager' in memory and reads
+//    commands line by line with rustyline, so the JSON file is parsed only once
+//    per session instead of once per command.
+//
+// 2) Each line is split into words (honouring "double" and 'single' quotes and
+//    backslash escapes) and parsed with the same Clap definition as the normal
+//    CLI, then routed through 'dispatch' like a one-shot command. Mutations are
+//    saved to disk immediately, exactly as outside the shell.
+//
+// 3) Line history is kept in ~/.todo_shell_history. Tab completes subcommand
+//    names, task IDs (showing their titles) and tags after '--tag', '--add-tag',
+//    '--remove-tag' or 'tag:'.
+//
+// 4) "exit", "quit" or Ctrl-D leave the shell; Ctrl-C clears the current line.
+//
+// Communication with Other Files:
+// - main.rs: Invokes 'handle_shell' when the user enters the "shell" subcommand.
+// - commands/mod.rs: 'dispatch' runs the handler of every parsed line.
+// - cli/app.rs: 'build_cli' provides the grammar used to parse each line.
+////////////////////////////////////////////////////////////////////////////////
+
+use std::path::PathBuf;
+use rustyline::completion::{Completer, Pair};
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+use crate::cli::app::build_cli;
+use crate::models::TaskManager;
+use super::dispatch;
+
+pub fn handle_shell(task_manager: &mut TaskManager) -> Result<(), Box<dyn std::error::Error>> {
+    let mut editor: Editor<ShellHelper, DefaultHistory> = Editor::new()?;
+    editor.set_helper(Some(ShellHelper::new(task_manager)));
+
+    let history_path = history_path();
+    if let Some(path) = &history_path {
+        // A missing history file just means this is the first session.
+        editor.load_history(path).ok();
+    }
+
+    println!("ToDo shell — type 'help' for commands, 'exit' to quit.");
+    loop {
+        let line = match editor.readline("todo> ") {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(e) => return Err(e.into()),
+        };
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        editor.add_history_entry(line)?;
+
+        if matches!(line, "exit" | "quit") {
+            break;
+        }
+        run_line(line, task_manager);
+
+        // IDs and tags may have changed; refresh what tab completion offers.
+        if let Some(helper) = editor.helper_mut() {
+            *helper = ShellHelper::new(task_manager);
+        }
+    }
+
+    if let Some(path) = &history_path {
+        editor.save_history(path)?;
+    }
+    Ok(())
+}
+
+/// Parses one shell line with the regular CLI definition and runs it.
+fn run_line(line: &str, task_manager: &mut TaskManager) {
+    let words = match split_words(line) {
+        Ok(words) => words,
+        Err(e) => {
+            println!("Error: {}", e);
+            return;
+        }
+    };
+    if words.first().map(String::as_str) == Some("shell") {
+        println!("Already in the shell");
+        return;
+    }
+
+    let cli = build_cli().no_binary_name(true);
+    match cli.try_get_matches_from(words) {
+        Ok(matches) => {
+            if let Err(e) = dispatch(&matches, task_manager) {
+                println!("Error: {}", e);
+            }
+        }
+        // Covers both real errors and --help / --version output.
+        Err(e) => {
+            e.print().ok();
+        }
+    }
+}
+
+/// Splits a line into words like a POSIX shell would for simple quoting.
+fn split_words(line: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut quote: Option<char> = None;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('"'), '\\') | (None, '\\') => {
+                current.push(chars.next().ok_or("Trailing backslash")?);
+                in_word = true;
+            }
+            (Some(_), c) => current.push(c),
+            (None, '"') | (None, '\'') => {
+                quote = Some(c);
+                in_word = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            (None, c) => {
+                current.push(c);
+                in_word = true;
+            }
+        }
+    }
+
+    if quote.is_some() {
+        return Err("Unterminated quote".to_string());
+    }
+    if in_word {
+        words.push(current);
+    }
+    Ok(words)
+}
+
+fn history_path() -> Option<PathBuf> {
+    ["HOME", "USERPROFILE"]
+        .iter()
+        .find_map(std::env::var_os)
+        .map(|home| PathBuf::from(home).join(".todo_shell_history"))
+}
+
+/// Tab completion data, rebuilt after every command from the TaskManager.
+struct ShellHelper {
+    subcommands: Vec<String>,
+    tasks: Vec<(u32, String)>,
+    tags: Vec<String>,
+}
+
+impl ShellHelper {
+    fn new(task_manager: &TaskManager) -> Self {
+        let mut subcommands: Vec<String> = build_cli()
+            .get_subcommands()
+            .map(|c| c.get_name().to_string())
+            .filter(|name| name != "shell")
+            .collect();
+        subcommands.extend(["help".to_string(), "exit".to_string()]);
+
+        let mut tags: Vec<String> = task_manager.tasks.iter().flat_map(|t| t.tags.clone()).collect();
+        tags.sort();
+        tags.dedup();
+
+        ShellHelper {
+            subcommands,
+            tasks: task_manager.tasks.iter().map(|t| (t.id, t.title.clone())).collect(),
+            tags,
+        }
+    }
+}
+
+impl Completer for ShellHelper {
+    type Candidate = Pair;
+
+    fn complete(&self, line: &str, pos: usize, _ctx: &Context<'_>) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let before = &line[..pos];
+        let start = before.rfind(char::is_whitespace).map(|i| i + 1).unwrap_or(0);
+        let word = &before[start..];
+        let previous = before[..start].split_whitespace().last();
+
+        let plain = |value: &String| Pair { display: value.clone(), replacement: value.clone() };
+
+        // First word: a subcommand name.
+        if previous.is_none() {
+            let candidates = self.subcommands.iter().filter(|c| c.starts_with(word)).map(plain).collect();
+            return Ok((start, candidates));
+        }
+
+        // Tags, either as a flag value or inside a --where expression.
+        if let Some(partial) = word.strip_prefix("tag:") {
+            let candidates = self.tags.iter()
+                .filter(|t| t.starts_with(partial))
+                .map(|t| Pair { display: t.clone(), replacement: format!("tag:{}", t) })
+                .collect();
+            return Ok((start, candidates));
+        }
+        if matches!(previous, Some("--tag") | Some("--add-tag") | Some("--remove-tag")) {
+            let candidates = self.tags.iter().filter(|t| t.starts_with(word)).map(plain).collect();
+            return Ok((start, candidates));
+        }
+
+        // Anything else that looks like the start of an ID.
+        if word.chars().all(|c| c.is_ascii_digit()) {
+            let candidates = self.tasks.iter()
+                .filter(|(id, _)| id.to_string().starts_with(word))
+                .map(|(id, title)| Pair { display: format!("{} ({})", id, title), replacement: id.to_string() })
+                .collect();
+            return Ok((start, candidates));
+        }
+
+        Ok((start, Vec::new()))
+    }
+}
+
+impl Hinter for ShellHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ShellHelper {}
+
+impl Validator for ShellHelper {}
+
+impl Helper for ShellHelper {}
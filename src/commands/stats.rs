@@ -0,0 +1,113 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file handles the "stats" subcommand for our ToDo application.
+//
+// 1) 'handle_stats' reports on the tasks (or those matching '--where'): open
+//    and done counts, the completion rate, how many open tasks are overdue and
+//    how old the open tasks are on average.
+//
+// 2) Throughput is the number of tasks completed in each of the last weeks
+//    (Monday to Sunday, the current week included), with the weekly average.
+//
+// 3) Breakdowns give the open and done counts per tag, priority and project
+//    (list). A task with several tags counts for each of them.
+//
+// 4) '--output json' prints the same figures as one JSON object for dashboards;
+//    ages are in days and weeks are named by their Monday.
+//
+// Communication with Other Files:
+// - main.rs: Invokes 'handle_stats' when the user enters the "stats" subcommand.
+// - models/Task: 'TaskStats::of' computes the figures.
+// - models/filter.rs: 'TaskFilter' parses and evaluates the '--where' expression.
+// - models/dates.rs: 'now' (which honours TODO_NOW) is the moment reported on.
+// - cli/app.rs: Declares the "stats" subcommand and its options.
+////////////////////////////////////////////////////////////////////////////////
+
+use chrono::{Duration, Utc};
+use clap::ArgMatches;
+use serde_json::{json, Map, Value};
+use crate::models::{Breakdown, Task, TaskFilter, TaskManager, TaskStats};
+use crate::models::dates::now;
+
+pub fn handle_stats(args: &ArgMatches, task_manager: &TaskManager) -> Result<(), Box<dyn std::error::Error>> {
+    // A bad filter is returned as an error, so scripts reading the JSON see a failing exit status.
+    let stats = match args.get_one::<String>("where").map(|w| TaskFilter::parse(w)).transpose()? {
+        Some(filter) => {
+            let tasks: Vec<Task> = task_manager.tasks.iter().filter(|t| filter.matches(t)).cloned().collect();
+            TaskStats::of(&tasks, now().with_timezone(&Utc))
+        }
+        None => task_manager.get_stats(),
+    };
+
+    match args.get_one::<String>("output").map(String::as_str) {
+        Some("json") => println!("{}", serde_json::to_string_pretty(&stats_json(&stats))?),
+        _ => print_stats(&stats),
+    }
+    Ok(())
+}
+
+fn print_stats(stats: &TaskStats) {
+    println!("\n=== Task statistics ===");
+    println!("Tasks:            {} ({} open, {} done)", stats.total, stats.open, stats.done);
+    println!("Completion rate:  {:.0}%", stats.completion_rate() * 100.0);
+    println!("Overdue:          {}", stats.overdue);
+    match stats.average_open_age {
+        Some(age) => println!("Average open age: {:.1} days", days(age)),
+        None => println!("Average open age: -"),
+    }
+
+    println!(
+        "\nCompleted per week (last {} weeks, {:.1} on average):",
+        stats.completed_per_week.len(),
+        average_per_week(stats)
+    );
+    for (monday, count) in &stats.completed_per_week {
+        println!("  {}  {}", monday.format("%Y-%m-%d"), count);
+    }
+
+    for (title, breakdown) in [("tag", &stats.by_tag), ("priority", &stats.by_priority), ("project", &stats.by_project)] {
+        println!("\nBy {}:", title);
+        let width = breakdown.iter().map(|b| b.name.chars().count()).max().unwrap_or(0);
+        for b in breakdown {
+            println!("  {:<width$}  {:>4} open  {:>4} done", b.name, b.open, b.done, width = width);
+        }
+    }
+}
+
+fn stats_json(stats: &TaskStats) -> Value {
+    json!({
+        "total": stats.total,
+        "open": stats.open,
+        "done": stats.done,
+        "completion_rate": stats.completion_rate(),
+        "overdue": stats.overdue,
+        "average_open_age_days": stats.average_open_age.map(days),
+        "throughput": {
+            "average_per_week": average_per_week(stats),
+            "weeks": stats
+                .completed_per_week
+                .iter()
+                .map(|(monday, count)| json!({ "week": monday.format("%Y-%m-%d").to_string(), "completed": count }))
+                .collect::<Vec<Value>>(),
+        },
+        "by_tag": breakdown_json(&stats.by_tag),
+        "by_priority": breakdown_json(&stats.by_priority),
+        "by_project": breakdown_json(&stats.by_project),
+    })
+}
+
+fn breakdown_json(breakdown: &[Breakdown]) -> Value {
+    let counts: Map<String, Value> = breakdown
+        .iter()
+        .map(|b| (b.name.clone(), json!({ "open": b.open, "done": b.done })))
+        .collect();
+    Value::Object(counts)
+}
+
+fn average_per_week(stats: &TaskStats) -> f64 {
+    let completed: usize = stats.completed_per_week.iter().map(|(_, count)| count).sum();
+    completed as f64 / stats.completed_per_week.len().max(1) as f64
+}
+
+fn days(duration: Duration) -> f64 {
+    duration.num_minutes() as f64 / (24.0 * 60.0)
+}
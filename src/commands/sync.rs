@@ -0,0 +1,165 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file handles the "sync" subcommand for our ToDo application.
+//
+// 1) 'sync --init' turns the data directory into a git repository. From then on
+//    TaskManager commits the task files after every change, with a message such
+//    as "Update task 3: Buy milk". 'sync --remote <url>' sets the remote to share
+//    with (any git URL, including a local bare repository).
+//
+// 2) A plain 'sync' commits anything left over, fetches the remote, and then:
+//    - pushes when the remote has nothing new,
+//    - fast-forwards when only the remote moved,
+//    - otherwise merges the two versions task by task (see models/merge.rs)
+//      against their common ancestor and records a regular git merge commit.
+//    The history logs are merged by keeping every entry of both sides.
+//    Finally the result is pushed back.
+//
+// 3) Because tasks are merged instead of lines, edits to different tasks or to
+//    different fields of a task never conflict, and tasks created on both sides
+//    under the same ID are renumbered above every ID either side ever issued
+//    (deleted tasks included, as the history logs record). A field both sides changed keeps the local
+//    value and the conflict is reported.
+//
+// 4) '--init' also registers the "merge" subcommand as git merge driver for the
+//    tasks file (and git's union merge for the history log), so a plain 'git pull'
+//    in the data directory merges the same way. Running it again in a cloned data
+//    directory sets the driver up there.
+//
+// Communication with Other Files:
+// - main.rs: Invokes 'handle_sync' when the user enters the "sync" subcommand.
+// - models/repo.rs: Runs git in the data directory.
+// - models/merge.rs: Merges diverged task lists and history logs.
+// - models/TaskManager: Provides the repository and reloads the merged tasks.
+// - cli/app.rs: Declares the "sync" subcommand and its options.
+////////////////////////////////////////////////////////////////////////////////
+
+use std::fs;
+use clap::ArgMatches;
+use crate::models::merge::{last_issued_in, merge_history, merge_tasks};
+use crate::models::repo::DataRepo;
+use crate::models::{Task, TaskManager};
+
+pub fn handle_sync(args: &ArgMatches, task_manager: &mut TaskManager) -> Result<(), Box<dyn std::error::Error>> {
+    let repo = task_manager.repo();
+    let init = args.get_flag("init");
+    let remote = args.get_one::<String>("remote");
+
+    if init {
+        let result = if repo.is_enabled() {
+            repo.install_merge_driver().map(|_| "Configured the merge driver of the existing repository")
+        } else {
+            repo.init().map(|_| "Initialized a git repository for the task files")
+        };
+        match result {
+            Ok(message) => println!("{}", message),
+            Err(e) => {
+                println!("Failed to initialize the repository: {}", e);
+                return Ok(());
+            }
+        }
+    }
+    if !repo.is_enabled() {
+        println!("The data directory is not versioned yet; run 'sync --init' first");
+        return Ok(());
+    }
+    if let Some(url) = remote {
+        match repo.set_remote(url) {
+            Ok(()) => println!("Remote set to {}", url),
+            Err(e) => println!("Failed to set the remote: {}", e),
+        }
+    }
+    if init || remote.is_some() {
+        return Ok(());
+    }
+
+    match task_manager.last_issued_id().and_then(|last_issued| sync(repo, last_issued)) {
+        Ok(conflicts) => {
+            for conflict in &conflicts {
+                println!("Conflict: {}", conflict);
+            }
+            task_manager.reload()?;
+        }
+        Err(e) => println!("Sync failed: {}", e),
+    }
+    Ok(())
+}
+
+/// Runs one sync round and returns the conflicts resolved while merging.
+/// `last_issued` is the highest ID this copy has given out (see `TaskManager::last_issued_id`).
+fn sync(repo: &DataRepo, last_issued: u32) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    repo.commit_all("Save local changes before sync")?;
+    if !repo.succeeds(&["remote", "get-url", "origin"]) {
+        return Err("no remote configured; use 'sync --remote <url>'".into());
+    }
+
+    let branch = repo.git(&["symbolic-ref", "--short", "HEAD"])?;
+    let remote_ref = format!("origin/{}", branch);
+    repo.git(&["fetch", "-q", "origin"])?;
+
+    let mut conflicts = Vec::new();
+    if !repo.succeeds(&["rev-parse", "--verify", "-q", &remote_ref])
+        || repo.succeeds(&["merge-base", "--is-ancestor", &remote_ref, "HEAD"])
+    {
+        println!("Nothing new on the remote");
+    } else if repo.succeeds(&["merge-base", "--is-ancestor", "HEAD", &remote_ref]) {
+        repo.git_as_author(&["merge", "-q", "--ff-only", &remote_ref])?;
+        println!("Fast-forwarded to {}", remote_ref);
+    } else {
+        conflicts = merge(repo, &remote_ref, last_issued)?;
+        println!("Merged the changes from {}", remote_ref);
+    }
+
+    repo.git(&["push", "-q", "origin", &format!("HEAD:refs/heads/{}", branch)])?;
+    println!("Pushed to origin/{}", branch);
+    Ok(conflicts)
+}
+
+/// Merges diverged histories task by task and commits the result as a git merge.
+fn merge(repo: &DataRepo, remote_ref: &str, last_issued: u32) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    // Copies set up separately share no ancestor; everything then counts as added.
+    let base = repo.git(&["merge-base", "HEAD", remote_ref]).ok();
+    let tasks_at = |rev: Option<&str>| -> Result<Vec<Task>, Box<dyn std::error::Error>> {
+        let text = match rev {
+            Some(rev) => repo.show(rev, repo.tasks_file())?,
+            None => None,
+        };
+        match text {
+            Some(text) if !text.trim().is_empty() => Ok(serde_json::from_str(&text)?),
+            _ => Ok(Vec::new()),
+        }
+    };
+    let history = merge_history(
+        &repo.show("HEAD", repo.history_file())?.unwrap_or_default(),
+        &repo.show(remote_ref, repo.history_file())?.unwrap_or_default(),
+    );
+    // IDs issued on either side, including those of since-deleted tasks, stay used.
+    let last_issued = last_issued.max(last_issued_in(&history));
+    let outcome = merge_tasks(
+        &tasks_at(base.as_deref())?,
+        &tasks_at(Some("HEAD"))?,
+        &tasks_at(Some(remote_ref))?,
+        last_issued,
+    );
+
+    // Start a merge that keeps our files, then replace them with the merged ones.
+    let mut start = vec!["merge", "-q", "--no-commit", "--no-ff", "-s", "ours", remote_ref];
+    if base.is_none() {
+        start.push("--allow-unrelated-histories");
+    }
+    repo.git_as_author(&start)?;
+    let finish = || -> Result<(), Box<dyn std::error::Error>> {
+        fs::write(repo.path_of(repo.tasks_file()), serde_json::to_string_pretty(&outcome.tasks)?)?;
+        fs::write(repo.path_of(repo.history_file()), &history)?;
+        repo.commit_all(&format!("Merge tasks from {}", remote_ref))?;
+        Ok(())
+    };
+    if let Err(e) = finish() {
+        // Leave the repository as it was rather than half-merged.
+        let _ = repo.git(&["merge", "--abort"]);
+        return Err(e);
+    }
+    for (old, new) in &outcome.renumbered {
+        println!("Task {} was created on both sides; the remote one is now task {}", old, new);
+    }
+    Ok(outcome.conflicts)
+}
@@ -0,0 +1,150 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file handles the "start", "stop" and "track" subcommands, which record
+// the time worked on tasks.
+//
+// 1) 'handle_start' starts a timer on one task (an ID or any reference such as
+//    '#last' or part of a title). Only one timer runs at a time: a timer running
+//    on another task is stopped first, in the same save.
+//
+// 2) 'handle_stop' stops the running timer and prints how long it ran.
+//
+// 3) 'handle_track' records time worked without a timer, e.g. "track 3 1h30m".
+//    The entry ends now, or starts at '--from' (e.g. "yesterday 14:00").
+//
+// 4) Every entry is a 'TimeEntry' on the task (start, and end once stopped), so
+//    "report time" can sum them per task, tag, project or day, and "read"
+//    shows the total.
+//
+// Communication with Other Files:
+// - main.rs: Invokes these handlers for the "start", "stop" and "track" subcommands.
+// - models/TaskManager: 'active_timer' finds the running timer, 'modify_tasks'
+//   records the entries.
+// - models/dates.rs: 'now' (which honours TODO_NOW), 'parse_duration' and
+//   'parse_past_date' read the times given.
+// - commands/selection.rs: 'select_one' resolves the task reference.
+// - cli/app.rs: Declares the three subcommands and their arguments.
+////////////////////////////////////////////////////////////////////////////////
+
+use chrono::Utc;
+use clap::ArgMatches;
+use crate::formats::display_timestamp;
+use crate::models::{TaskManager, TimeEntry};
+use crate::models::dates::{format_duration, now, parse_duration, parse_past_date};
+use super::selection::select_one;
+
+pub fn handle_start(args: &ArgMatches, task_manager: &mut TaskManager) -> Result<(), Box<dyn std::error::Error>> {
+    let id = match select_one(args.get_one::<String>("id").expect("Required"), task_manager) {
+        Ok(id) => id,
+        Err(e) => {
+            println!("Error: {}", e);
+            return Ok(());
+        }
+    };
+    let now = now().with_timezone(&Utc);
+
+    let running = task_manager.active_timer().map(|t| t.id);
+    if running == Some(id) {
+        let since = task_manager.read_task(id)?.running_since().expect("timer is running");
+        println!("The timer of task {} is already running since {}", id, display_timestamp(since));
+        return Ok(());
+    }
+
+    let ids: Vec<u32> = running.into_iter().chain([id]).collect();
+    let result = task_manager.modify_tasks(&ids, |task| {
+        if task.id == id {
+            task.time_entries.push(TimeEntry { start: now, end: None });
+        } else if let Some(entry) = task.time_entries.iter_mut().find(|e| e.end.is_none()) {
+            entry.end = Some(now);
+        }
+    });
+    match result {
+        Ok(tasks) => {
+            for task in tasks {
+                if task.id == id {
+                    println!("Started timer on task {} - {}", task.id, task.title);
+                } else {
+                    let last = task.time_entries.iter().rfind(|e| e.end == Some(now)).map(|e| e.within(None, now));
+                    println!(
+                        "Stopped timer on task {} - {} after {}",
+                        task.id, task.title, format_duration(last.unwrap_or_default())
+                    );
+                }
+            }
+        }
+        Err(e) => println!("Error: {}", e),
+    }
+    Ok(())
+}
+
+pub fn handle_stop(task_manager: &mut TaskManager) -> Result<(), Box<dyn std::error::Error>> {
+    let Some(id) = task_manager.active_timer().map(|t| t.id) else {
+        println!("No timer is running");
+        return Ok(());
+    };
+    let now = now().with_timezone(&Utc);
+
+    let result = task_manager.modify_tasks(&[id], |task| {
+        if let Some(entry) = task.time_entries.iter_mut().find(|e| e.end.is_none()) {
+            entry.end = Some(now);
+        }
+    });
+    match result {
+        Ok(tasks) => {
+            for task in tasks {
+                let last = task.time_entries.iter().rfind(|e| e.end == Some(now)).map(|e| e.within(None, now));
+                println!(
+                    "Stopped timer on task {} - {} after {} (total {})",
+                    task.id,
+                    task.title,
+                    format_duration(last.unwrap_or_default()),
+                    format_duration(task.time_tracked(now))
+                );
+            }
+        }
+        Err(e) => println!("Error: {}", e),
+    }
+    Ok(())
+}
+
+pub fn handle_track(args: &ArgMatches, task_manager: &mut TaskManager) -> Result<(), Box<dyn std::error::Error>> {
+    let id = select_one(args.get_one::<String>("id").expect("Required"), task_manager);
+    let duration = parse_duration(args.get_one::<String>("duration").expect("Required"));
+    let from = args.get_one::<String>("from").map(|v| parse_past_date(v)).transpose();
+    let (id, duration, from) = match (id, duration, from) {
+        (Ok(id), Ok(duration), Ok(from)) => (id, duration, from),
+        (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => {
+            println!("Error: {}", e);
+            return Ok(());
+        }
+    };
+    let now = now().with_timezone(&Utc);
+    let entry = match from {
+        Some(start) => start.checked_add_signed(duration).map(|end| (start, end)),
+        None => now.checked_sub_signed(duration).map(|start| (start, now)),
+    };
+    let Some((start, end)) = entry else {
+        println!("Error: {} is too long to track", format_duration(duration));
+        return Ok(());
+    };
+
+    let result = task_manager.modify_tasks(&[id], |task| {
+        // Keep the entries in order even when tracking time worked in the past.
+        let position = task.time_entries.partition_point(|e| e.start <= start);
+        task.time_entries.insert(position, TimeEntry { start, end: Some(end) });
+    });
+    match result {
+        Ok(tasks) => {
+            for task in tasks {
+                println!(
+                    "Tracked {} on task {} - {} (total {})",
+                    format_duration(duration),
+                    task.id,
+                    task.title,
+                    format_duration(task.time_tracked(now))
+                );
+            }
+        }
+        Err(e) => println!("Error: {}", e),
+    }
+    Ok(())
+}
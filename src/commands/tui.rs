@@ -0,0 +1,22 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file handles the "tui" subcommand for our ToDo application.
+//
+// 1) The 'handle_tui' function starts the full-screen terminal interface from
+//    the 'tui' module, passing it the already loaded 'task_manager'.
+//
+// 2) Every change made in the interface (create, edit, done, delete) goes
+//    through the same TaskManager methods as the one-shot subcommands, so the
+//    JSON file and the audit log are updated as usual.
+//
+// Communication with Other Files:
+// - main.rs: Invokes 'handle_tui' when the user enters the "tui" subcommand.
+// - tui/: Implements the interface itself (state, key handling and drawing).
+// - cli/app.rs: Declares the "tui" subcommand.
+////////////////////////////////////////////////////////////////////////////////
+
+use crate::models::TaskManager;
+use crate::tui;
+
+pub fn handle_tui(task_manager: &mut TaskManager) -> Result<(), Box<dyn std::error::Error>> {
+    tui::run(task_manager)
+}
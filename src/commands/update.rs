@@ -1,52 +1,124 @@
 ////////////////////////////////////////////////////////////////////////////////
 // This file handles the "update" subcommand of the ToDo application.
 //
-// 1) We parse the 'id' from ArgMatches, ensuring it's a valid positive number.
-// 2) We fetch the existing task via 'task_manager.read_task(id)', if it exists.
-// 3) We then check 'title' and 'description' from ArgMatches:
-//    - If the user didn't supply them, we keep the old values from existing_task.
-// 4) We call 'task_manager.update_task(id, title, description)' to apply changes 
-//    and save the updated data to JSON.
+// 1) We resolve the task IDs from ArgMatches: one or more IDs or ranges and/or
+//    a '--where' filter (see commands/selection.rs).
+// 2) We then check 'title', 'description', '--add-tag', '--remove-tag', '--due',
+//    '--scheduled' and '--estimate':
+//    - If the user didn't supply a field, the old value is kept.
+//    - Dates take the forms of models/dates.rs ("friday 9am", "in 2 weeks"),
+//      estimates a duration or points ("2h", "3pt"); "none" clears them.
+// 3) With '--dry-run' we only print the field-level changes. Otherwise, above
+//    the confirmation threshold we ask before continuing.
+// 4) We call 'task_manager.modify_tasks(ids, ...)' to apply changes to every
+//    selected task and save the updated data to JSON once.
 // 5) Finally, we print the success/error message accordingly.
 //
 // Communication with Other Files:
 // - main.rs: Invokes 'handle_update' when "update" subcommand is chosen.
-// - models::TaskManager: The 'read_task' and 'update_task' methods are defined here, 
-//   performing lookups, updates, and JSON file writes.
+// - models::TaskManager: The 'modify_tasks' and 'preview_modify' methods are
+//   defined here, performing lookups, updates, and JSON file writes.
 // - cli/app.rs: Defines the "update" subcommand and optional title/description flags.
 //   This ensures that if they are not passed, the existing fields remain unchanged.
 //
 // Example Flow: 
 //   cargo run update 1 -t "New Title" -d "New Desc"
+//   cargo run update --where "tag:sprint-4" --add-tag review
+//   cargo run update 3 --due "next friday"
 
-//   main.rs → handle_update → read_task → update_task → prints result.
+//   main.rs → handle_update → modify_tasks → prints result.
 ////////////////////////////////////////////////////////////////////////////////
 
+use chrono::{DateTime, Utc};
 use clap::ArgMatches;
-use crate::models::TaskManager;
+use crate::models::{Task, TaskManager};
+use crate::models::dates::parse_human_date;
+use super::create::estimate_arg;
+use super::selection::{confirm, print_preview, select_ids};
 
 pub fn handle_update(args: &ArgMatches, task_manager: &mut TaskManager) -> Result<(), Box<dyn std::error::Error>> {
-    let id = args.get_one::<String>("id")
-        .expect("ID is required")
-        .parse::<u32>()
-        .unwrap_or_else(|_| {
-            println!("Error: ID must be a positive number");
-            std::process::exit(1);
-        });
-
-    let existing_task = task_manager.read_task(id)?;
-
-    let title = args.get_one::<String>("title")
-        .map(|s| s.to_string())
-        .unwrap_or(existing_task.title);
-
-    let description = args.get_one::<String>("description")
-        .map(|s| s.to_string())
-        .unwrap_or(existing_task.description);
-
-    match task_manager.update_task(id, title, description) {
-        Ok(task) => println!("Task updated successfully: {:?}", task),
+    let ids = match select_ids(args, task_manager) {
+        Ok(ids) => ids,
+        Err(e) => {
+            println!("Error: {}", e);
+            return Ok(());
+        }
+    };
+    if ids.is_empty() {
+        println!("No tasks matched");
+        return Ok(());
+    }
+
+    let title = args.get_one::<String>("title");
+    let description = args.get_one::<String>("description");
+    let add_tags: Vec<&String> = args.get_many::<String>("add-tag").map(|v| v.collect()).unwrap_or_default();
+    let remove_tags: Vec<&String> = args.get_many::<String>("remove-tag").map(|v| v.collect()).unwrap_or_default();
+    let estimate = match args.get_one::<String>("estimate").map(String::as_str) {
+        Some(value) if value.eq_ignore_ascii_case("none") => Ok(Some(None)),
+        Some(value) => estimate_arg(value).map(|e| Some(Some(e))),
+        None => Ok(None),
+    };
+    let (due, scheduled, estimate) = match (date_arg(args, "due"), date_arg(args, "scheduled"), estimate) {
+        (Ok(due), Ok(scheduled), Ok(estimate)) => (due, scheduled, estimate),
+        (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => {
+            println!("Error: {}", e);
+            return Ok(());
+        }
+    };
+
+    let apply = |task: &mut Task| {
+        if let Some(title) = title {
+            task.title = title.to_string();
+        }
+        if let Some(description) = description {
+            task.description = description.to_string();
+        }
+        for tag in &add_tags {
+            task.add_tag(tag);
+        }
+        for tag in &remove_tags {
+            task.remove_tag(tag);
+        }
+        if let Some(due) = due {
+            task.due = due;
+        }
+        if let Some(scheduled) = scheduled {
+            task.scheduled = scheduled;
+        }
+        if let Some(estimate) = estimate {
+            task.estimate = estimate;
+        }
+    };
+
+    if args.get_flag("dry-run") {
+        match task_manager.preview_modify(&ids, apply) {
+            Ok(changes) => print_preview(&changes),
+            Err(e) => println!("Error updating task: {}", e),
+        }
+        return Ok(());
+    }
+
+    if !confirm(args, "update", ids.len()) {
+        println!("Aborted");
+        return Ok(());
+    }
+
+    match task_manager.modify_tasks(&ids, apply) {
+        Ok(tasks) => {
+            for task in tasks {
+                println!("Task updated successfully: {:?}", task);
+            }
+        }
         Err(e) => println!("Error updating task: {}", e),
     }
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Reads a date option: `None` when absent, `Some(None)` for "none" (clear it).
+fn date_arg(args: &ArgMatches, name: &str) -> Result<Option<Option<DateTime<Utc>>>, Box<dyn std::error::Error>> {
+    match args.get_one::<String>(name).map(String::as_str) {
+        None => Ok(None),
+        Some(value) if value.eq_ignore_ascii_case("none") => Ok(Some(None)),
+        Some(value) => Ok(Some(Some(parse_human_date(value)?))),
+    }
+}
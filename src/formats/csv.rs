@@ -0,0 +1,85 @@
+use crate::models::Task;
+
+/// Task fields that can be filled from a CSV column.
+pub const FIELDS: [&str; 4] = ["title", "description", "tags", "done"];
+
+/// Maps task fields to CSV column names. Fields without an explicit mapping use
+/// the column with the same name (case-insensitive), if there is one.
+#[derive(Debug, Clone, Default)]
+pub struct ColumnMap {
+    columns: Vec<(String, String)>,
+}
+
+impl ColumnMap {
+    /// Parses `--map title=Summary,description=Notes`.
+    pub fn parse(spec: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut columns = Vec::new();
+        for pair in spec.split(',').filter(|p| !p.trim().is_empty()) {
+            let (field, column) = pair.split_once('=')
+                .ok_or_else(|| format!("Invalid mapping '{}', expected field=Column", pair))?;
+            let field = field.trim().to_lowercase();
+            if !FIELDS.contains(&field.as_str()) {
+                return Err(format!("Unknown field '{}', expected one of: {}", field, FIELDS.join(", ")).into());
+            }
+            columns.push((field, column.trim().to_string()));
+        }
+        Ok(ColumnMap { columns })
+    }
+
+    fn column_for<'a>(&'a self, field: &'a str) -> &'a str {
+        self.columns
+            .iter()
+            .find(|(f, _)| f == field)
+            .map(|(_, column)| column.as_str())
+            .unwrap_or(field)
+    }
+}
+
+/// Reads tasks from CSV text with a header row. A title column is required;
+/// rows with an empty title are skipped. Tags may be separated by commas or
+/// semicolons, and `done` accepts yes/no, true/false, x or 1/0.
+pub fn parse(text: &str, map: &ColumnMap) -> Result<Vec<Task>, Box<dyn std::error::Error>> {
+    let mut reader = ::csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(::csv::Trim::All)
+        .from_reader(text.as_bytes());
+
+    let headers = reader.headers()?.clone();
+    let index_of = |field: &str| {
+        let column = map.column_for(field);
+        headers.iter().position(|h| h.eq_ignore_ascii_case(column))
+    };
+
+    let title = index_of("title").ok_or_else(|| {
+        format!(
+            "No '{}' column found (columns: {}); use --map title=<Column>",
+            map.column_for("title"),
+            headers.iter().collect::<Vec<_>>().join(", ")
+        )
+    })?;
+    let description = index_of("description");
+    let tags = index_of("tags");
+    let done = index_of("done");
+
+    let mut tasks = Vec::new();
+    for record in reader.records() {
+        let record = record?;
+        let cell = |index: Option<usize>| index.and_then(|i| record.get(i)).unwrap_or("");
+
+        let title = cell(Some(title));
+        if title.is_empty() {
+            continue;
+        }
+        let mut task = Task {
+            title: title.to_string(),
+            description: cell(description).to_string(),
+            done: matches!(cell(done).to_lowercase().as_str(), "yes" | "true" | "x" | "1" | "done"),
+            ..Task::default()
+        };
+        for tag in cell(tags).split([',', ';']).map(str::trim).filter(|t| !t.is_empty()) {
+            task.add_tag(tag);
+        }
+        tasks.push(task);
+    }
+    Ok(tasks)
+}
@@ -0,0 +1,248 @@
+use std::collections::HashMap;
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use uuid::Uuid;
+use crate::models::Task;
+use super::{from_local_date, local_date, overlay};
+
+/// Suffix of the UIDs older versions generated ("task-3@todo-rust-cli"); such
+/// files are still matched back to task IDs.
+const UID_DOMAIN: &str = "@todo-rust-cli";
+
+/// The fields a VTODO carries; an update keeps the others.
+const CARRIED: [&str; 9] =
+    ["title", "description", "due", "done", "priority", "tags", "recurrence", "created", "completed"];
+
+/// Renders tasks as an iCalendar file with one VTODO per task:
+/// SUMMARY ← title, DESCRIPTION ← description, DUE, STATUS, PRIORITY,
+/// CATEGORIES ← tags, RRULE ← recurrence, UID ← task UUID, plus CREATED/COMPLETED.
+pub fn render(tasks: &[Task]) -> String {
+    let now = format_utc(Utc::now());
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//todo-rust-cli//EN".to_string(),
+    ];
+
+    for task in tasks {
+        lines.push("BEGIN:VTODO".to_string());
+        lines.push(format!("UID:{}", task.uuid));
+        lines.push(format!("DTSTAMP:{}", now));
+        lines.push(format!("SUMMARY:{}", escape(&task.title)));
+        if !task.description.is_empty() {
+            lines.push(format!("DESCRIPTION:{}", escape(&task.description)));
+        }
+        if let Some(due) = task.due {
+            lines.push(format_due(due));
+        }
+        lines.push(format!("STATUS:{}", if task.done { "COMPLETED" } else { "NEEDS-ACTION" }));
+        if let Some(priority) = task.priority {
+            lines.push(format!("PRIORITY:{}", priority_number(priority)));
+        }
+        if !task.tags.is_empty() {
+            let tags: Vec<String> = task.tags.iter().map(|t| escape(t)).collect();
+            lines.push(format!("CATEGORIES:{}", tags.join(",")));
+        }
+        if let Some(rule) = &task.recurrence {
+            lines.push(format!("RRULE:{}", rule));
+        }
+        if let Some(created) = task.created {
+            lines.push(format!("CREATED:{}", format_utc(created)));
+        }
+        if let Some(completed) = task.completed {
+            lines.push(format!("COMPLETED:{}", format_utc(completed)));
+        }
+        lines.push("END:VTODO".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    lines.iter().map(|line| fold(line)).collect::<Vec<_>>().join("")
+}
+
+/// Reads every VTODO of an iCalendar file into tasks. VTODOs whose UID is the
+/// UUID of an existing task become updates of that task; fields iCalendar does
+/// not carry (such as the project, notes or estimate) keep their stored values,
+/// as does the priority letter while it maps to the same PRIORITY. New tasks
+/// whose UID is a UUID keep it.
+pub fn parse(text: &str, existing: &[Task]) -> Result<Vec<Task>, Box<dyn std::error::Error>> {
+    let by_id: HashMap<u32, &Task> = existing.iter().map(|t| (t.id, t)).collect();
+    let by_uuid: HashMap<Uuid, u32> = existing.iter().map(|t| (t.uuid, t.id)).collect();
+    let mut tasks = Vec::new();
+    let mut current: Option<(Task, Option<u32>)> = None;
+
+    for line in unfold(text) {
+        let (name, params, value) = split_property(&line);
+        match (name.as_str(), current.as_mut()) {
+            ("BEGIN", None) if value.eq_ignore_ascii_case("VTODO") => current = Some((Task::default(), None)),
+            ("END", Some(_)) if value.eq_ignore_ascii_case("VTODO") => {
+                let (mut parsed, id) = current.take().expect("inside a VTODO");
+                if parsed.title.trim().is_empty() {
+                    return Err("VTODO without a SUMMARY".into());
+                }
+                tasks.push(match id.and_then(|id| by_id.get(&id)) {
+                    Some(stored) => {
+                        parsed.created = parsed.created.or(stored.created);
+                        // Letters below B all export as 9; keep the stored one while the number matches.
+                        parsed.priority = match (parsed.priority, stored.priority) {
+                            (Some(p), Some(s)) if priority_number(p) == priority_number(s) => Some(s),
+                            (p, _) => p,
+                        };
+                        overlay(stored, &parsed, &CARRIED)?
+                    }
+                    None => parsed,
+                });
+            }
+            (_, None) => {}
+            ("UID", Some((task, id))) => match Uuid::parse_str(value.trim()) {
+                Ok(uuid) => {
+                    *id = by_uuid.get(&uuid).copied();
+                    task.uuid = uuid;
+                }
+                Err(_) => {
+                    *id = value
+                        .strip_suffix(UID_DOMAIN)
+                        .and_then(|v| v.strip_prefix("task-"))
+                        .and_then(|v| v.parse().ok());
+                }
+            },
+            ("SUMMARY", Some((task, _))) => task.title = unescape(&value),
+            ("DESCRIPTION", Some((task, _))) => task.description = unescape(&value),
+            ("DUE", Some((task, _))) => task.due = parse_timestamp(&value, &params),
+            ("STATUS", Some((task, _))) => task.done = value.eq_ignore_ascii_case("COMPLETED"),
+            ("PRIORITY", Some((task, _))) => task.priority = value.trim().parse().ok().and_then(priority_letter),
+            ("CATEGORIES", Some((task, _))) => {
+                for tag in split_unescaped(&value).iter().filter(|t| !t.is_empty()) {
+                    task.add_tag(tag);
+                }
+            }
+            ("RRULE", Some((task, _))) => task.recurrence = Some(value.clone()),
+            ("CREATED", Some((task, _))) => task.created = parse_timestamp(&value, &params),
+            ("COMPLETED", Some((task, _))) => task.completed = parse_timestamp(&value, &params),
+            _ => {}
+        }
+    }
+    Ok(tasks)
+}
+
+/// RFC 5545 priorities: 1 is highest, 9 lowest, 0 undefined.
+fn priority_number(letter: char) -> u8 {
+    match letter {
+        'A' => 1,
+        'B' => 5,
+        _ => 9,
+    }
+}
+
+fn priority_letter(number: u8) -> Option<char> {
+    match number {
+        1..=4 => Some('A'),
+        5 => Some('B'),
+        6..=9 => Some('C'),
+        _ => None,
+    }
+}
+
+/// Dates without a time (local midnight) are written as all-day values.
+fn format_due(due: DateTime<Utc>) -> String {
+    if due.with_timezone(&Local).time() == chrono::NaiveTime::MIN {
+        format!("DUE;VALUE=DATE:{}", local_date(due).format("%Y%m%d"))
+    } else {
+        format!("DUE:{}", format_utc(due))
+    }
+}
+
+fn format_utc(timestamp: DateTime<Utc>) -> String {
+    timestamp.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Accepts DATE values, UTC date-times ("...Z") and floating or TZID date-times,
+/// which are read as local time.
+fn parse_timestamp(value: &str, params: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    if params.to_uppercase().contains("VALUE=DATE") || value.len() == 8 {
+        return NaiveDate::parse_from_str(value, "%Y%m%d").ok().map(from_local_date);
+    }
+    if let Some(utc) = value.strip_suffix('Z') {
+        return NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok().map(|t| t.and_utc());
+    }
+    let naive = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?;
+    Local.from_local_datetime(&naive).earliest().map(|t| t.with_timezone(&Utc))
+}
+
+/// Splits "NAME;PARAM=X:value" into (upper-cased name, params, value).
+fn split_property(line: &str) -> (String, String, String) {
+    let (head, value) = line.split_once(':').unwrap_or((line, ""));
+    let (name, params) = head.split_once(';').unwrap_or((head, ""));
+    (name.trim().to_uppercase(), params.to_string(), value.to_string())
+}
+
+/// Joins folded continuation lines (starting with a space or tab) back together.
+fn unfold(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw in text.lines() {
+        let raw = raw.trim_end_matches('\r');
+        match (raw.strip_prefix(' ').or_else(|| raw.strip_prefix('\t')), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ if raw.is_empty() => {}
+            _ => lines.push(raw.to_string()),
+        }
+    }
+    lines
+}
+
+/// Folds a content line at 75 octets, as required by RFC 5545, and ends it with CRLF.
+fn fold(line: &str) -> String {
+    let mut out = String::new();
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+    out
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+fn unescape(text: &str) -> String {
+    let mut out = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => {}
+        }
+    }
+    out
+}
+
+/// Splits a comma-separated list value, honouring escaped commas.
+fn split_unescaped(value: &str) -> Vec<String> {
+    let mut parts = vec![String::new()];
+    let mut escaped = false;
+    for c in value.chars() {
+        match (escaped, c) {
+            (false, '\\') => escaped = true,
+            (false, ',') => parts.push(String::new()),
+            (true, c) => {
+                parts.last_mut().expect("never empty").push(if c == 'n' { '\n' } else { c });
+                escaped = false;
+            }
+            (false, c) => parts.last_mut().expect("never empty").push(c),
+        }
+    }
+    parts.into_iter().map(|p| p.trim().to_string()).collect()
+}
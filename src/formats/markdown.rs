@@ -0,0 +1,185 @@
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+use crate::models::Task;
+use super::keep_outside_parents;
+
+/// Renders tasks as a GitHub-style checklist, grouped under one heading per
+/// project (falling back to the first tag). Descriptions follow their item as
+/// indented text and subtasks are nested below their parent. Each item ends with
+/// an `<!-- id:N uuid:U -->` comment, invisible once rendered, so the file can be
+/// imported back as updates.
+pub fn render(tasks: &[Task]) -> String {
+    let ids: HashSet<u32> = tasks.iter().map(|t| t.id).collect();
+    let mut children: HashMap<u32, Vec<&Task>> = HashMap::new();
+    let mut groups: Vec<(String, Vec<&Task>)> = Vec::new();
+
+    for task in tasks {
+        match task.parent.filter(|p| ids.contains(p) && *p != task.id) {
+            Some(parent) => children.entry(parent).or_default().push(task),
+            None => {
+                let name = group_name(task);
+                match groups.iter_mut().find(|(n, _)| *n == name) {
+                    Some((_, members)) => members.push(task),
+                    None => groups.push((name, vec![task])),
+                }
+            }
+        }
+    }
+
+    let mut out = String::from("# Tasks\n");
+    let mut written = HashSet::new();
+    for (name, members) in &groups {
+        out.push_str(&format!("\n## {}\n\n", name));
+        for task in members {
+            render_item(&mut out, task, 0, &children, &mut written);
+        }
+    }
+    out
+}
+
+/// Reads `- [ ]` / `- [x]` items into tasks. Items nested under another item
+/// become its subtasks; indented text below an item is its description, and
+/// trailing `#words` are tags. Headings and other text are ignored.
+///
+/// Items carrying the UUID of an existing task in their comment (or, in files
+/// without UUIDs, its `id:N`) update it, keeping the fields the checklist does
+/// not show (priority, project, dates). New items get
+/// provisional IDs above every stored one so their subtasks can point at them;
+/// 'create_tasks' swaps them for real IDs.
+pub fn parse(text: &str, existing: &[Task]) -> Result<Vec<Task>, Box<dyn std::error::Error>> {
+    let by_id: HashMap<u32, &Task> = existing.iter().map(|t| (t.id, t)).collect();
+    let by_uuid: HashMap<Uuid, &Task> = existing.iter().map(|t| (t.uuid, t)).collect();
+    let mut next_id = existing.iter().map(|t| t.id).max().unwrap_or(0) + 1;
+    let mut items: Vec<(Task, bool)> = Vec::new();
+    // Indentation and item index of the items enclosing the current line.
+    let mut open: Vec<(usize, usize)> = Vec::new();
+    let mut blank_lines = 0;
+
+    for line in text.lines() {
+        let indent = line.len() - line.trim_start().len();
+        let content = line.trim();
+        if content.is_empty() {
+            blank_lines += 1;
+            continue;
+        }
+
+        if let Some((done, rest)) = checkbox(content) {
+            open.retain(|(i, _)| *i < indent);
+            let (title, tags, id, uuid) = split_item(rest);
+            if title.is_empty() {
+                return Err(format!("Task without a title: '{}'", content).into());
+            }
+
+            let stored = match uuid {
+                Some(uuid) => by_uuid.get(&uuid),
+                None => id.and_then(|id| by_id.get(&id)),
+            };
+            let mut task = match stored {
+                Some(stored) => Task {
+                    title,
+                    description: String::new(),
+                    done,
+                    tags: Vec::new(),
+                    ..(*stored).clone()
+                },
+                None => {
+                    next_id += 1;
+                    Task { id: next_id - 1, title, done, uuid: uuid.unwrap_or_default(), ..Task::default() }
+                }
+            };
+            for tag in tags {
+                task.add_tag(tag);
+            }
+            task.parent = open.last().map(|&(_, index)| items[index].0.id);
+            items.push((task, open.is_empty()));
+            open.push((indent, items.len() - 1));
+        } else if indent > 0 {
+            open.retain(|(i, _)| *i < indent);
+            if let Some(&(item_indent, index)) = open.last() {
+                let description = &mut items[index].0.description;
+                if !description.is_empty() {
+                    description.push_str(&"\n".repeat(blank_lines + 1));
+                }
+                let text = line.get(item_indent + 2..).filter(|_| indent >= item_indent + 2).unwrap_or(content);
+                description.push_str(text.trim_end());
+            }
+        } else {
+            open.clear();
+        }
+        blank_lines = 0;
+    }
+
+    Ok(keep_outside_parents(items, existing))
+}
+
+fn group_name(task: &Task) -> String {
+    task.project
+        .clone()
+        .or_else(|| task.tags.first().cloned())
+        .unwrap_or_else(|| "Other".to_string())
+}
+
+fn render_item(
+    out: &mut String,
+    task: &Task,
+    depth: usize,
+    children: &HashMap<u32, Vec<&Task>>,
+    written: &mut HashSet<u32>,
+) {
+    // Guards against parent cycles, which would otherwise recurse forever.
+    if !written.insert(task.id) {
+        return;
+    }
+    let indent = "  ".repeat(depth);
+    let mark = if task.done { "x" } else { " " };
+    let tags: String = task.tags.iter().map(|t| format!(" #{}", t)).collect();
+    out.push_str(&format!("{}- [{}] {}{} <!-- id:{} uuid:{} -->\n", indent, mark, task.title, tags, task.id, task.uuid));
+
+    for line in task.description.lines() {
+        if line.trim().is_empty() {
+            out.push('\n');
+        } else {
+            out.push_str(&format!("{}  {}\n", indent, line));
+        }
+    }
+    for child in children.get(&task.id).into_iter().flatten() {
+        render_item(out, child, depth + 1, children, written);
+    }
+}
+
+/// Recognises `- [ ] text`, `* [x] text` and `+ [X] text`.
+fn checkbox(content: &str) -> Option<(bool, &str)> {
+    let rest = content.strip_prefix(['-', '*', '+'])?.strip_prefix(' ')?.trim_start();
+    let (mark, rest) = rest.strip_prefix('[')?.split_once(']')?;
+    let done = match mark {
+        " " | "" => false,
+        "x" | "X" => true,
+        _ => return None,
+    };
+    Some((done, rest.trim()))
+}
+
+/// Splits an item into its title, trailing `#tags` and the ID and UUID of its
+/// `<!-- id:N uuid:U -->` comment.
+fn split_item(text: &str) -> (String, Vec<&str>, Option<u32>, Option<Uuid>) {
+    let mut text = text.trim();
+    let (mut id, mut uuid) = (None, None);
+    if let Some(start) = text.rfind("<!--").filter(|_| text.ends_with("-->")) {
+        for key in text[start + 4..text.len() - 3].split_whitespace() {
+            if let Some(value) = key.strip_prefix("id:") {
+                id = value.parse().ok();
+            } else if let Some(value) = key.strip_prefix("uuid:") {
+                uuid = Uuid::parse_str(value).ok();
+            }
+        }
+        text = text[..start].trim_end();
+    }
+
+    let mut words: Vec<&str> = text.split_whitespace().collect();
+    let mut tags = Vec::new();
+    while let Some(tag) = words.last().and_then(|w| w.strip_prefix('#')).filter(|t| t.starts_with(char::is_alphabetic)) {
+        tags.insert(0, tag);
+        words.pop();
+    }
+    (words.join(" "), tags, id, uuid)
+}
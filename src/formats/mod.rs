@@ -0,0 +1,128 @@
+///////////////////////////////////////////////////////////////////////////////
+// This 'mod.rs' file is the entry point for the 'formats' module, which converts
+// between our Task list and other file formats for the "import" and "export"
+// subcommands.
+//
+// 1) Each submodule handles one format and only deals with text ↔ Task
+//    conversion; it never touches the TaskManager. Imported tasks are returned
+//    as drafts: an ID of 0 means "new task" (assigned later by 'create_tasks'),
+//    while a known ID means the draft is an updated version of that task. Any
+//    other ID is provisional: a new task that other drafts refer to as 'parent'.
+//    Updates are built with 'overlay', so the fields a format cannot express
+//    keep their stored values.
+//
+// 2) 'Format' lists the supported formats, so the CLI and the commands can
+//    validate '--format' in one place.
+//
+// 3) The date helpers below convert between stored UTC timestamps and the
+//    local calendar dates most formats use.
+///////////////////////////////////////////////////////////////////////////////
+
+pub mod csv;
+pub mod todotxt;
+pub mod ical;
+pub mod markdown;
+pub mod taskwarrior;
+pub mod org;
+
+use std::collections::HashSet;
+use chrono::{DateTime, Local, NaiveDate, TimeZone, Utc};
+use serde_json::{Map, Value};
+use crate::models::Task;
+
+/// A file format tasks can be imported from or exported to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Csv,
+    TodoTxt,
+    ICal,
+    Markdown,
+    Taskwarrior,
+    Org,
+}
+
+impl Format {
+    /// Names accepted by `import --format`.
+    pub const IMPORT_NAMES: [&'static str; 6] = ["csv", "todotxt", "ical", "markdown", "taskwarrior", "org"];
+    /// Names accepted by `export --format`.
+    pub const EXPORT_NAMES: [&'static str; 5] = ["todotxt", "ical", "markdown", "taskwarrior", "org"];
+
+    pub fn from_name(name: &str) -> Option<Format> {
+        match name.to_lowercase().as_str() {
+            "csv" => Some(Format::Csv),
+            "todotxt" | "todo.txt" => Some(Format::TodoTxt),
+            "ical" | "ics" => Some(Format::ICal),
+            "markdown" | "md" => Some(Format::Markdown),
+            "taskwarrior" | "tw" => Some(Format::Taskwarrior),
+            "org" => Some(Format::Org),
+            _ => None,
+        }
+    }
+}
+
+/// Lays the `fields` a format carries (their JSON names) from `parsed` over the
+/// stored task it updates. Every other field keeps its stored value, including
+/// fields added after the format was written; the ID and UUID never change.
+pub fn overlay(stored: &Task, parsed: &Task, fields: &[&str]) -> Result<Task, String> {
+    let parsed = match serde_json::to_value(parsed) {
+        Ok(Value::Object(map)) => map,
+        _ => return Err("Task did not serialize to an object".to_string()),
+    };
+    let patch: Map<String, Value> = fields
+        .iter()
+        .map(|field| (field.to_string(), parsed.get(*field).cloned().unwrap_or(Value::Null)))
+        .collect();
+    stored.patched(&Value::Object(patch))
+}
+
+/// The local calendar date of a stored timestamp.
+pub fn local_date(timestamp: DateTime<Utc>) -> NaiveDate {
+    timestamp.with_timezone(&Local).date_naive()
+}
+
+/// Local midnight of a calendar date, as stored in the task file.
+pub fn from_local_date(date: NaiveDate) -> DateTime<Utc> {
+    let midnight = date.and_hms_opt(0, 0, 0).expect("midnight is valid");
+    Local
+        .from_local_datetime(&midnight)
+        .earliest()
+        .map(|local| local.with_timezone(&Utc))
+        .unwrap_or_else(|| midnight.and_utc())
+}
+
+/// Formats a stored timestamp in local time for display: just the date for a
+/// local midnight (a date without a time), otherwise date and time.
+pub fn display_timestamp(timestamp: DateTime<Utc>) -> String {
+    let local = timestamp.with_timezone(&Local);
+    if local.time() == chrono::NaiveTime::MIN {
+        local.format("%Y-%m-%d").to_string()
+    } else {
+        local.format("%Y-%m-%d %H:%M").to_string()
+    }
+}
+
+/// Parses a `YYYY-MM-DD` date into a stored timestamp.
+pub fn parse_date(text: &str) -> Option<DateTime<Utc>> {
+    NaiveDate::parse_from_str(text, "%Y-%m-%d").ok().map(from_local_date)
+}
+
+/// Finishes the items of an outline format (Markdown, Org), given with a flag
+/// telling whether they sit at the top level. Nested items already point at their
+/// parent; top-level ones keep a stored parent that is not part of the file, e.g.
+/// when only some tasks were exported with '--where'.
+pub fn keep_outside_parents(items: Vec<(Task, bool)>, existing: &[Task]) -> Vec<Task> {
+    let in_file: HashSet<u32> = items.iter().map(|(t, _)| t.id).collect();
+    items
+        .into_iter()
+        .map(|(mut task, top_level)| {
+            if top_level {
+                task.parent = existing
+                    .iter()
+                    .find(|t| t.id == task.id)
+                    .and_then(|stored| stored.parent)
+                    .filter(|p| !in_file.contains(p));
+            }
+            task
+        })
+        .collect()
+}
@@ -0,0 +1,360 @@
+use std::collections::{HashMap, HashSet};
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use uuid::Uuid;
+use crate::models::Task;
+use super::{from_local_date, keep_outside_parents, overlay};
+
+/// Property holding our task ID. Org's own `:ID:` holds the task UUID, as org-id
+/// expects.
+const ID_PROPERTY: &str = "TASK_ID";
+const UUID_PROPERTY: &str = "ID";
+
+/// The fields an Org entry always carries; an update keeps the others.
+const CARRIED: [&str; 9] =
+    ["title", "description", "done", "priority", "tags", "parent", "due", "scheduled", "completed"];
+
+/// Renders tasks as an Org outline: one `TODO`/`DONE` headline per task with its
+/// `[#A]` priority and `:tags:`, a planning line for `DEADLINE` (due),
+/// `SCHEDULED` and `CLOSED` (completed), a property drawer with the ID, UUID,
+/// project (`CATEGORY`) and creation time, then the description. Subtasks are nested.
+pub fn render(tasks: &[Task]) -> String {
+    let ids: HashSet<u32> = tasks.iter().map(|t| t.id).collect();
+    let mut children: HashMap<u32, Vec<&Task>> = HashMap::new();
+    let mut roots = Vec::new();
+    for task in tasks {
+        match task.parent.filter(|p| ids.contains(p) && *p != task.id) {
+            Some(parent) => children.entry(parent).or_default().push(task),
+            None => roots.push(task),
+        }
+    }
+
+    let mut out = String::from("#+TITLE: Tasks\n#+TODO: TODO | DONE\n\n");
+    let mut written = HashSet::new();
+    for task in roots {
+        render_entry(&mut out, task, 1, &children, &mut written);
+    }
+    out
+}
+
+/// Reads the `TODO`/`DONE` headlines of an Org file into tasks. Headlines nested
+/// under a task become its subtasks; headlines without a keyword only group tasks
+/// and are skipped. Text below the planning line and drawers is the description.
+///
+/// Headlines carrying the `:ID:` (UUID) or, failing that, the `:TASK_ID:` of an
+/// existing task update it, keeping the fields Org does not show (recurrence,
+/// notes, estimate, ...), the project and creation time when their property is
+/// missing, and the exact timestamps when the file shows the same minute. New
+/// headlines get provisional IDs above every stored one so their subtasks can
+/// point at them.
+pub fn parse(text: &str, existing: &[Task]) -> Result<Vec<Task>, Box<dyn std::error::Error>> {
+    let by_id: HashMap<u32, &Task> = existing.iter().map(|t| (t.id, t)).collect();
+    let by_uuid: HashMap<Uuid, u32> = existing.iter().map(|t| (t.uuid, t.id)).collect();
+    let mut next_id = existing.iter().map(|t| t.id).max().unwrap_or(0) + 1;
+    let mut entries: Vec<Entry> = Vec::new();
+    // Level and entry index of the task headlines enclosing the current line.
+    let mut open: Vec<(usize, usize)> = Vec::new();
+    // Whether the lines being read belong to the last entry (not a plain section).
+    let mut in_entry = false;
+
+    for line in text.lines() {
+        if let Some((level, rest)) = headline(line) {
+            open.retain(|(l, _)| *l < level);
+            in_entry = false;
+            let Some(mut entry) = Entry::parse_headline(rest) else {
+                continue;
+            };
+            if entry.task.title.is_empty() {
+                return Err(format!("Task without a title: '{}'", line).into());
+            }
+            entry.parent = open.last().map(|&(_, index)| index);
+            entries.push(entry);
+            open.push((level, entries.len() - 1));
+            in_entry = true;
+        } else if in_entry {
+            entries.last_mut().expect("in an entry").read_line(line);
+        }
+    }
+
+    // Resolve IDs first so subtasks can point at their parent's ID.
+    for entry in entries.iter_mut() {
+        let uuid = entry.property(UUID_PROPERTY).and_then(|v| Uuid::parse_str(v).ok());
+        let known = match uuid {
+            Some(uuid) => by_uuid.get(&uuid).copied(),
+            None => entry.property(ID_PROPERTY).and_then(|v| v.parse().ok()).filter(|id| by_id.contains_key(id)),
+        };
+        entry.task.uuid = uuid.unwrap_or_default();
+        entry.task.id = known.unwrap_or_else(|| {
+            next_id += 1;
+            next_id - 1
+        });
+    }
+    let ids: Vec<u32> = entries.iter().map(|e| e.task.id).collect();
+
+    let items = entries
+        .into_iter()
+        .map(|entry| {
+            let top_level = entry.parent.is_none();
+            let mut task = entry.finish();
+            task.parent = entry.parent.map(|index| ids[index]);
+            if let Some(stored) = by_id.get(&task.id) {
+                task.created = keep_precise(task.created, stored.created);
+                task.completed = keep_precise(task.completed, stored.completed);
+                task.due = keep_precise(task.due, stored.due);
+                task.scheduled = keep_precise(task.scheduled, stored.scheduled);
+                // A drawer without CATEGORY or CREATED leaves the project or creation time as stored.
+                let mut carried = CARRIED.to_vec();
+                carried.extend(entry.property("CATEGORY").map(|_| "project"));
+                carried.extend(entry.property("CREATED").map(|_| "created"));
+                task = overlay(stored, &task, &carried)?;
+            }
+            Ok((task, top_level))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    Ok(keep_outside_parents(items, existing))
+}
+
+/// A task headline with the lines that followed it.
+struct Entry {
+    task: Task,
+    parent: Option<usize>,
+    properties: Vec<(String, String)>,
+    body: Vec<String>,
+    in_drawer: bool,
+    in_properties: bool,
+}
+
+impl Entry {
+    /// Parses "TODO [#A] Title :tag1:tag2:"; `None` for headlines without a keyword.
+    fn parse_headline(rest: &str) -> Option<Entry> {
+        let (keyword, rest) = rest.split_once(' ').unwrap_or((rest, ""));
+        let done = match keyword {
+            "TODO" => false,
+            "DONE" => true,
+            _ => return None,
+        };
+        let mut task = Task { done, ..Task::default() };
+
+        let mut rest = rest.trim();
+        if let Some(after) = rest.strip_prefix("[#") {
+            if let Some((priority, after)) = after.split_once(']') {
+                task.priority = priority.chars().next().map(|c| c.to_ascii_uppercase());
+                rest = after.trim_start();
+            }
+        }
+        if let Some((title, tags)) = rest.rsplit_once(char::is_whitespace) {
+            if tags.len() > 1 && tags.starts_with(':') && tags.ends_with(':') {
+                for tag in tags.split(':').filter(|t| !t.is_empty()) {
+                    task.add_tag(tag);
+                }
+                rest = title.trim_end();
+            }
+        }
+        task.title = rest.to_string();
+
+        Some(Entry {
+            task,
+            parent: None,
+            properties: Vec::new(),
+            body: Vec::new(),
+            in_drawer: false,
+            in_properties: false,
+        })
+    }
+
+    fn read_line(&mut self, line: &str) {
+        let trimmed = line.trim();
+        if self.in_drawer {
+            if trimmed.eq_ignore_ascii_case(":END:") {
+                self.in_drawer = false;
+                self.in_properties = false;
+            } else if self.in_properties {
+                if let Some((key, value)) = trimmed.strip_prefix(':').and_then(|p| p.split_once(':')) {
+                    self.properties.push((key.to_uppercase(), value.trim().to_string()));
+                }
+            }
+            return;
+        }
+        if self.body.is_empty() {
+            if is_drawer_start(trimmed) {
+                self.in_drawer = true;
+                self.in_properties = trimmed.eq_ignore_ascii_case(":PROPERTIES:");
+                return;
+            }
+            if self.read_planning(trimmed) {
+                return;
+            }
+            if trimmed.is_empty() {
+                return;
+            }
+        }
+        self.body.push(line.to_string());
+    }
+
+    /// Reads `DEADLINE:`, `SCHEDULED:` and `CLOSED:` from a planning line.
+    fn read_planning(&mut self, line: &str) -> bool {
+        let mut found = false;
+        for (keyword, field) in [
+            ("DEADLINE:", &mut self.task.due),
+            ("SCHEDULED:", &mut self.task.scheduled),
+            ("CLOSED:", &mut self.task.completed),
+        ] {
+            if let Some(after) = line.find(keyword).map(|i| &line[i + keyword.len()..]) {
+                *field = parse_timestamp(after.trim_start());
+                found = true;
+            }
+        }
+        found
+    }
+
+    fn property(&self, key: &str) -> Option<&str> {
+        self.properties.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str())
+    }
+
+    fn finish(&self) -> Task {
+        let mut task = self.task.clone();
+        task.project = self.property("CATEGORY").filter(|c| !c.is_empty()).map(str::to_string);
+        task.created = self.property("CREATED").and_then(parse_timestamp);
+
+        let lines: Vec<&str> = self.body.iter().map(|l| l.trim_end()).collect();
+        let indent = lines
+            .iter()
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| l.len() - l.trim_start().len())
+            .min()
+            .unwrap_or(0);
+        let lines: Vec<String> = lines
+            .iter()
+            .map(|l| unescape_line(l.get(indent..).unwrap_or("")).to_string())
+            .collect();
+        task.description = lines.join("\n").trim_matches('\n').to_string();
+        task
+    }
+}
+
+fn render_entry(
+    out: &mut String,
+    task: &Task,
+    level: usize,
+    children: &HashMap<u32, Vec<&Task>>,
+    written: &mut HashSet<u32>,
+) {
+    // Guards against parent cycles, which would otherwise recurse forever.
+    if !written.insert(task.id) {
+        return;
+    }
+    out.push_str(&format!("{} {} ", "*".repeat(level), if task.done { "DONE" } else { "TODO" }));
+    if let Some(priority) = task.priority {
+        out.push_str(&format!("[#{}] ", priority));
+    }
+    out.push_str(&task.title);
+    if !task.tags.is_empty() {
+        let tags: Vec<String> = task.tags.iter().map(|t| tag_name(t)).collect();
+        out.push_str(&format!(" :{}:", tags.join(":")));
+    }
+    out.push('\n');
+
+    let mut planning = Vec::new();
+    if let Some(completed) = task.completed.filter(|_| task.done) {
+        planning.push(format!("CLOSED: [{}]", format_timestamp(completed, true)));
+    }
+    if let Some(due) = task.due {
+        planning.push(format!("DEADLINE: <{}>", format_timestamp(due, false)));
+    }
+    if let Some(scheduled) = task.scheduled {
+        planning.push(format!("SCHEDULED: <{}>", format_timestamp(scheduled, false)));
+    }
+    if !planning.is_empty() {
+        out.push_str(&format!("{}\n", planning.join(" ")));
+    }
+
+    out.push_str(&format!(":PROPERTIES:\n:{}: {}\n", ID_PROPERTY, task.id));
+    out.push_str(&format!(":{}: {}\n", UUID_PROPERTY, task.uuid));
+    if let Some(project) = &task.project {
+        out.push_str(&format!(":CATEGORY: {}\n", project));
+    }
+    if let Some(created) = task.created {
+        out.push_str(&format!(":CREATED: [{}]\n", format_timestamp(created, true)));
+    }
+    out.push_str(":END:\n");
+
+    for line in task.description.lines() {
+        out.push_str(&escape_line(line));
+        out.push('\n');
+    }
+    for child in children.get(&task.id).into_iter().flatten() {
+        render_entry(out, child, level + 1, children, written);
+    }
+}
+
+/// Splits "** rest" into (2, "rest").
+fn headline(line: &str) -> Option<(usize, &str)> {
+    let level = line.len() - line.trim_start_matches('*').len();
+    let rest = line[level..].strip_prefix(' ')?;
+    (level > 0).then_some((level, rest.trim()))
+}
+
+fn is_drawer_start(line: &str) -> bool {
+    line.len() > 2
+        && line.starts_with(':')
+        && line.ends_with(':')
+        && line[1..line.len() - 1].chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Org tags may only contain letters, digits, `_`, `@`, `#` and `%`.
+fn tag_name(tag: &str) -> String {
+    tag.chars()
+        .map(|c| if c.is_alphanumeric() || "_@#%".contains(c) { c } else { '_' })
+        .collect()
+}
+
+/// Body lines that Org would read as a headline or keyword are escaped with a comma.
+fn escape_line(line: &str) -> String {
+    if line.starts_with('*') || line.starts_with("#+") || line.starts_with(',') {
+        format!(",{}", line)
+    } else {
+        line.to_string()
+    }
+}
+
+fn unescape_line(line: &str) -> &str {
+    match line.strip_prefix(',') {
+        Some(rest) if rest.starts_with('*') || rest.starts_with("#+") || rest.starts_with(',') => rest,
+        _ => line,
+    }
+}
+
+/// `2026-11-03 Tue` for a date without a time, `2026-11-03 Tue 14:00` otherwise.
+fn format_timestamp(timestamp: DateTime<Utc>, with_time: bool) -> String {
+    let local = timestamp.with_timezone(&Local);
+    if with_time || local.time() != NaiveTime::MIN {
+        local.format("%Y-%m-%d %a %H:%M").to_string()
+    } else {
+        local.format("%Y-%m-%d %a").to_string()
+    }
+}
+
+/// Reads the date and optional time of an active `<...>` or inactive `[...]`
+/// timestamp at the start of `text`; weekdays and repeaters are ignored.
+fn parse_timestamp(text: &str) -> Option<DateTime<Utc>> {
+    let inner = text.strip_prefix(['<', '['])?;
+    let inner = &inner[..inner.find(['>', ']'])?];
+    let mut words = inner.split_whitespace();
+    let date = NaiveDate::parse_from_str(words.next()?, "%Y-%m-%d").ok()?;
+    let time = words.find_map(|w| NaiveTime::parse_from_str(w.split('-').next().unwrap_or(w), "%H:%M").ok());
+
+    match time {
+        Some(time) => Local
+            .from_local_datetime(&NaiveDateTime::new(date, time))
+            .earliest()
+            .map(|t| t.with_timezone(&Utc)),
+        None => Some(from_local_date(date)),
+    }
+}
+
+/// Keeps the stored timestamp when the file shows it to the minute.
+fn keep_precise(parsed: Option<DateTime<Utc>>, stored: Option<DateTime<Utc>>) -> Option<DateTime<Utc>> {
+    match (parsed, stored) {
+        (Some(p), Some(s)) if p.timestamp() / 60 == s.timestamp() / 60 => Some(s),
+        _ => parsed,
+    }
+}
@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::models::{Note, Task};
+use super::overlay;
+
+/// The fields a Taskwarrior record carries; an update keeps the others.
+const CARRIED: [&str; 9] = ["title", "done", "project", "priority", "due", "created", "completed", "tags", "notes"];
+
+/// One task in the shape of `task export`. Attributes we have no field for
+/// (urgency, depends, wait, UDAs, ...) are ignored on import.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Record {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    id: Option<u32>,
+    #[serde(default)]
+    uuid: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    entry: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    end: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    due: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    project: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    priority: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    annotations: Vec<Annotation>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Annotation {
+    entry: String,
+    description: String,
+}
+
+/// Renders tasks as a JSON array `task import` accepts. Our title is the
+/// Taskwarrior description; our description, which Taskwarrior has no field for,
+/// becomes the first annotation, followed by the notes.
+pub fn render(tasks: &[Task]) -> Result<String, serde_json::Error> {
+    let records: Vec<Record> = tasks
+        .iter()
+        .map(|task| {
+            let mut annotations = Vec::new();
+            if !task.description.trim().is_empty() {
+                annotations.push(Annotation {
+                    entry: format_timestamp(task.created.unwrap_or_else(Utc::now)),
+                    description: task.description.clone(),
+                });
+            }
+            annotations.extend(task.notes.iter().map(|note| Annotation {
+                entry: format_timestamp(note.entry),
+                description: note.text.clone(),
+            }));
+
+            Record {
+                id: Some(task.id),
+                uuid: task.uuid.to_string(),
+                description: task.title.clone(),
+                status: if task.done { "completed" } else { "pending" }.to_string(),
+                entry: Some(format_timestamp(task.created.unwrap_or_else(Utc::now))),
+                end: task.done.then(|| format_timestamp(task.completed.unwrap_or_else(Utc::now))),
+                due: task.due.map(format_timestamp),
+                project: task.project.clone(),
+                priority: task.priority.map(|p| priority_level(p).to_string()),
+                tags: task.tags.clone(),
+                annotations,
+            }
+        })
+        .collect();
+    let mut text = serde_json::to_string_pretty(&records)?;
+    text.push('\n');
+    Ok(text)
+}
+
+/// Reads `task export` output: a JSON array, or one JSON object per line as
+/// older versions print. Deleted tasks and recurrence templates are skipped.
+///
+/// Tasks whose UUID matches an existing task (e.g. our own export imported back)
+/// update it, keeping the fields Taskwarrior has no attribute for (description,
+/// scheduled date, estimate, ...) and the exact timestamps when the file shows
+/// the same second. New tasks keep their Taskwarrior UUID.
+pub fn parse(text: &str, existing: &[Task]) -> Result<Vec<Task>, Box<dyn std::error::Error>> {
+    let records: Vec<Record> = if text.trim_start().starts_with('[') {
+        serde_json::from_str(text)?
+    } else {
+        text.lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .map(serde_json::from_str)
+            .collect::<Result<_, _>>()?
+    };
+    let by_uuid: HashMap<Uuid, &Task> = existing.iter().map(|t| (t.uuid, t)).collect();
+    let mut tasks = Vec::new();
+
+    for record in records {
+        if matches!(record.status.as_str(), "deleted" | "recurring") {
+            continue;
+        }
+        if record.description.trim().is_empty() {
+            return Err(format!("Task {} has no description", record.uuid).into());
+        }
+        let uuid = Uuid::parse_str(&record.uuid).unwrap_or_default();
+        let stored = by_uuid.get(&uuid).copied();
+
+        let mut task = Task {
+            title: record.description.trim().to_string(),
+            done: record.status == "completed",
+            project: record.project.filter(|p| !p.is_empty()),
+            priority: record.priority.as_deref().and_then(priority_letter),
+            due: record.due.as_deref().and_then(parse_timestamp),
+            created: record.entry.as_deref().and_then(parse_timestamp),
+            completed: record.end.as_deref().and_then(parse_timestamp),
+            uuid,
+            ..Task::default()
+        };
+        for tag in &record.tags {
+            task.add_tag(tag);
+        }
+        task.notes = record
+            .annotations
+            .iter()
+            .filter_map(|a| Some(Note { entry: parse_timestamp(&a.entry)?, text: a.description.clone() }))
+            .collect();
+
+        tasks.push(match stored {
+            Some(stored) => {
+                // Our description went out as the first annotation; don't turn it into a note.
+                task.notes = task
+                    .notes
+                    .into_iter()
+                    .filter(|n| n.text != stored.description)
+                    .map(|n| {
+                        let same = |s: &&Note| s.text == n.text && same_second(s.entry, n.entry);
+                        stored.notes.iter().find(same).cloned().unwrap_or(n)
+                    })
+                    .collect();
+                // Letters below C all export as L; keep the stored one while the level matches.
+                task.priority = match (task.priority, stored.priority) {
+                    (Some(p), Some(s)) if priority_level(p) == priority_level(s) => Some(s),
+                    (p, _) => p,
+                };
+                task.due = keep_precise(task.due, stored.due);
+                // Without a stored creation time, the export made one up for `entry`.
+                task.created = stored.created.map(|s| keep_precise(task.created, Some(s)).unwrap_or(s));
+                task.completed = keep_precise(task.completed, stored.completed);
+                overlay(stored, &task, &CARRIED)?
+            }
+            None => task,
+        });
+    }
+    Ok(tasks)
+}
+
+/// Taskwarrior only knows H, M and L.
+fn priority_level(letter: char) -> &'static str {
+    match letter {
+        'A' => "H",
+        'B' => "M",
+        _ => "L",
+    }
+}
+
+fn priority_letter(level: &str) -> Option<char> {
+    match level {
+        "H" => Some('A'),
+        "M" => Some('B'),
+        "L" => Some('C'),
+        _ => None,
+    }
+}
+
+fn format_timestamp(timestamp: DateTime<Utc>) -> String {
+    timestamp.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Accepts Taskwarrior's `20261103T140000Z` as well as RFC 3339.
+fn parse_timestamp(text: &str) -> Option<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(text, "%Y%m%dT%H%M%SZ")
+        .map(|t| t.and_utc())
+        .ok()
+        .or_else(|| DateTime::parse_from_rfc3339(text).ok().map(|t| t.with_timezone(&Utc)))
+}
+
+fn same_second(a: DateTime<Utc>, b: DateTime<Utc>) -> bool {
+    a.timestamp() == b.timestamp()
+}
+
+/// Keeps the stored timestamp when the file shows it to the second.
+fn keep_precise(parsed: Option<DateTime<Utc>>, stored: Option<DateTime<Utc>>) -> Option<DateTime<Utc>> {
+    match (parsed, stored) {
+        (Some(p), Some(s)) if same_second(p, s) => Some(s),
+        _ => parsed,
+    }
+}
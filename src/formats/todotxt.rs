@@ -0,0 +1,198 @@
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use crate::models::Task;
+use super::{local_date, overlay, parse_date};
+
+/// The fields a todo.txt line carries; an update keeps the others.
+const CARRIED: [&str; 8] = ["title", "done", "priority", "project", "tags", "due", "created", "completed"];
+
+/// Reads todo.txt lines into tasks. Mapping:
+/// - `x` marker and the dates after it → `done`, `completed`, `created`
+/// - `(A)` or `pri:A` → `priority`
+/// - first `+project` → `project`; further projects and `@contexts` → `tags`
+/// - `due:YYYY-MM-DD` → `due`
+/// - `uuid:U` → matched against `existing`, so an exported file can be edited and
+///   imported back as updates instead of duplicates, even into another copy where
+///   the task has a different ID; files without one fall back to `id:N`
+///
+/// Other `key:value` pairs are kept in the title so nothing is lost, and a word
+/// starting with `\` is title text with the backslash removed (see `escape`).
+/// Fields todo.txt cannot express (e.g. the description, notes or estimate) keep
+/// their stored values.
+pub fn parse(text: &str, existing: &[Task]) -> Result<Vec<Task>, Box<dyn std::error::Error>> {
+    let by_id: HashMap<u32, &Task> = existing.iter().map(|t| (t.id, t)).collect();
+    let by_uuid: HashMap<Uuid, &Task> = existing.iter().map(|t| (t.uuid, t)).collect();
+    let mut tasks = Vec::new();
+
+    for line in text.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        let mut words: Vec<&str> = line.split_whitespace().collect();
+        let mut parsed = Task::default();
+
+        if words.first() == Some(&"x") {
+            words.remove(0);
+            parsed.done = true;
+            if let Some(date) = words.first().and_then(|w| parse_date(w)) {
+                parsed.completed = Some(date);
+                words.remove(0);
+            }
+        } else if let Some(priority) = words.first().and_then(|w| parse_priority(w)) {
+            parsed.priority = Some(priority);
+            words.remove(0);
+        }
+        if let Some(date) = words.first().and_then(|w| parse_date(w)) {
+            parsed.created = Some(date);
+            words.remove(0);
+        }
+
+        let (mut id, mut uuid) = (None, None);
+        let mut title = Vec::new();
+        for word in words {
+            if let Some(literal) = word.strip_prefix('\\') {
+                title.push(literal);
+            } else if let Some(project) = word.strip_prefix('+').filter(|p| !p.is_empty()) {
+                if parsed.project.is_none() {
+                    parsed.project = Some(project.to_string());
+                } else {
+                    parsed.add_tag(project);
+                }
+            } else if let Some(context) = word.strip_prefix('@').filter(|c| !c.is_empty()) {
+                parsed.add_tag(context);
+            } else if let Some(date) = word.strip_prefix("due:").and_then(parse_date) {
+                parsed.due = Some(date);
+            } else if let Some(priority) = word.strip_prefix("pri:").and_then(|p| p.chars().next()) {
+                parsed.priority = Some(priority.to_ascii_uppercase());
+            } else if let Some(value) = word.strip_prefix("id:").and_then(|v| v.parse::<u32>().ok()) {
+                id = Some(value);
+            } else if let Some(value) = word.strip_prefix("uuid:").and_then(|v| Uuid::parse_str(v).ok()) {
+                uuid = Some(value);
+            } else {
+                title.push(word);
+            }
+        }
+        parsed.title = title.join(" ");
+        if parsed.title.is_empty() {
+            return Err(format!("Task without a title: '{}'", line).into());
+        }
+
+        // Known tasks keep the fields todo.txt does not carry, and their exact
+        // timestamps when the file still shows the same day.
+        let stored = match uuid {
+            Some(uuid) => by_uuid.get(&uuid),
+            None => id.and_then(|id| by_id.get(&id)),
+        };
+        parsed.uuid = uuid.unwrap_or_default();
+        tasks.push(match stored {
+            Some(stored) => {
+                parsed.created = same_day_or(parsed.created, stored.created).or(stored.created);
+                parsed.completed = same_day_or(parsed.completed, stored.completed);
+                parsed.due = same_day_or(parsed.due, stored.due);
+                overlay(stored, &parsed, &CARRIED)?
+            }
+            None => parsed,
+        });
+    }
+    Ok(tasks)
+}
+
+/// Writes one todo.txt line per task, including `id:` and `uuid:` keys for
+/// round-tripping.
+pub fn render(tasks: &[Task]) -> String {
+    let mut out = String::new();
+    for task in tasks {
+        let mut words: Vec<String> = Vec::new();
+        if task.done {
+            words.push("x".to_string());
+            // todo.txt requires a completion date before the creation date.
+            if let Some(completed) = task.completed.or(task.created) {
+                words.push(local_date(completed).to_string());
+            }
+        } else if let Some(priority) = task.priority {
+            words.push(format!("({})", priority));
+        }
+        if let Some(created) = task.created {
+            if !task.done || task.completed.is_some() {
+                words.push(local_date(created).to_string());
+            }
+        }
+
+        words.extend(task.title.split_whitespace().enumerate().map(|(i, word)| escape(word, i == 0)));
+        if let Some(project) = &task.project {
+            words.push(format!("+{}", project.replace(' ', "-")));
+        }
+        for tag in &task.tags {
+            words.push(format!("@{}", tag.replace(' ', "-")));
+        }
+        if let Some(due) = task.due {
+            words.push(format!("due:{}", local_date(due)));
+        }
+        // Completed tasks lose the "(A)" prefix, so keep their priority as a key.
+        if let (true, Some(priority)) = (task.done, task.priority) {
+            words.push(format!("pri:{}", priority));
+        }
+        words.push(format!("id:{}", task.id));
+        words.push(format!("uuid:{}", task.uuid));
+
+        out.push_str(&words.join(" "));
+        out.push('\n');
+    }
+    out
+}
+
+/// Prefixes a title word with `\` when 'parse' would otherwise read it as a
+/// project, context or key (or, as the first word, as the done marker, a priority
+/// or a date), so titles such as "Email +bob about due:2026-11-01" survive a
+/// round-trip. Words already starting with a backslash are escaped too.
+fn escape(word: &str, first: bool) -> String {
+    let key = word.strip_prefix(['+', '@']).is_some_and(|rest| !rest.is_empty())
+        || word.strip_prefix("due:").and_then(parse_date).is_some()
+        || word.strip_prefix("pri:").is_some_and(|p| !p.is_empty())
+        || word.strip_prefix("id:").is_some_and(|v| v.parse::<u32>().is_ok())
+        || word.strip_prefix("uuid:").is_some_and(|v| Uuid::parse_str(v).is_ok());
+    let prefix = first && (word == "x" || parse_priority(word).is_some() || parse_date(word).is_some());
+    if key || prefix || word.starts_with('\\') {
+        format!("\\{}", word)
+    } else {
+        word.to_string()
+    }
+}
+
+/// Prefers the stored timestamp when the parsed date-only value falls on the same local day.
+fn same_day_or(parsed: Option<DateTime<Utc>>, stored: Option<DateTime<Utc>>) -> Option<DateTime<Utc>> {
+    match (parsed, stored) {
+        (Some(p), Some(s)) if local_date(p) == local_date(s) => Some(s),
+        _ => parsed,
+    }
+}
+
+fn parse_priority(word: &str) -> Option<char> {
+    let inner = word.strip_prefix('(')?.strip_suffix(')')?;
+    let mut chars = inner.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if c.is_ascii_uppercase() => Some(c),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn titles_with_todotxt_syntax_round_trip() {
+        for title in [
+            "Email +bob @home about due:2026-11-01",
+            "x marks the spot",
+            "(A) team plan",
+            "2026-10-19 retro notes",
+            "Rename pri:A and id:3 keys",
+            r"Escape \+literal backslashes",
+        ] {
+            let task = Task { id: 1, title: title.to_string(), ..Task::default() };
+            let parsed = parse(&render(&[task]), &[]).unwrap().remove(0);
+            assert_eq!(parsed.title, title);
+            assert!(!parsed.done && parsed.priority.is_none() && parsed.project.is_none());
+            assert!(parsed.tags.is_empty() && parsed.due.is_none() && parsed.created.is_none());
+        }
+    }
+}
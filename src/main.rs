@@ -26,38 +26,53 @@
 //    Clap (see `cli/app.rs`). It defines available subcommands, arguments, 
 //    and options.
 //
-// 4) In `main`, we create a `TaskManager` instance pointing to `data/todos.json`,
-//    then build the CLI commands via `cli::app::build_cli()`. We parse and match 
-//    the user’s input. Then we dispatch each subcommand to its matching function 
-//    in the `commands` module.
+// 4) In `main`, we build the CLI commands via `cli::app::build_cli()` and parse
+//    the user’s input, then create a `TaskManager` instance pointing to
+//    `data/todos.json`. `commands::dispatch` matches each subcommand to its
+//    handler function in the `commands` module. The "shell" subcommand instead
+//    keeps the TaskManager loaded and dispatches one line at a time, and "merge"
+//    (a git merge driver working on the files it is given) runs before any store
+//    is loaded, exiting with status 1 on conflicts.
 //
 // 5) This design cleanly separates concerns: 
 //    - `models` for data and logic
 //    - `commands` for specific actions 
 //    - `cli` for argument parsing 
+//    - `tui` for the full-screen interface started by the "tui" subcommand
+//    - `server` for the HTTP API started by the "serve" subcommand
+//    - `rpc` for the JSON-RPC channel started by the "rpc" subcommand
+//    - `formats` for converting tasks to and from other file formats
 //    - `main` for coordinating everything.
 ///////////////////////////////////////////////////////////////////////////////
 
 mod models;
 mod commands;
 mod cli;
+mod tui;
+mod server;
+mod rpc;
+mod formats;
 
 use models::TaskManager;
-use commands::{handle_create, handle_read, handle_update, handle_delete, handle_list};
+use commands::{dispatch, handle_merge, handle_shell};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let mut task_manager = TaskManager::new("data/todos.json")?;
-    
     let cli = cli::app::build_cli();
     let matches = cli.get_matches();
 
+    // "merge" works on the files it is given, often from git outside our data directory.
+    if let Some(("merge", args)) = matches.subcommand() {
+        if !handle_merge(args)? {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    let mut task_manager = TaskManager::new("data/todos.json")?;
+
     match matches.subcommand() {
-        Some(("create", args)) => handle_create(args, &mut task_manager)?,
-        Some(("read", args)) => handle_read(args, &task_manager)?,
-        Some(("update", args)) => handle_update(args, &mut task_manager)?,
-        Some(("delete", args)) => handle_delete(args, &mut task_manager)?,
-        Some(("list", _)) => handle_list( &task_manager)?,
-        _ => println!("No subcommand was used. Use --help for usage information."),
+        Some(("shell", _)) => handle_shell(&mut task_manager)?,
+        _ => dispatch(&matches, &mut task_manager)?,
     }
 
     Ok(())
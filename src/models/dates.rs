@@ -0,0 +1,330 @@
+use chrono::{DateTime, Datelike, Duration, Local, Months, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike, Utc, Weekday};
+
+/// Environment variable fixing "now" (e.g. `2026-10-19 09:00`), so relative dates
+/// resolve the same way on every run.
+pub const NOW_VARIABLE: &str = "TODO_NOW";
+
+/// The current local time, or the one set in `TODO_NOW`.
+pub fn now() -> DateTime<Local> {
+    std::env::var(NOW_VARIABLE)
+        .ok()
+        .and_then(|value| absolute(value.trim()))
+        .map(|timestamp| timestamp.with_timezone(&Local))
+        .unwrap_or_else(Local::now)
+}
+
+/// Parses a date the way people type it, relative to `now()`. See `parse_human_date_at`.
+pub fn parse_human_date(text: &str) -> Result<DateTime<Utc>, Box<dyn std::error::Error>> {
+    parse_human_date_at(text, now())
+}
+
+/// Like `parse_human_date`, for dates that lie behind us (`--since monday`): a
+/// bare weekday is the latest one, today included, instead of the next one.
+pub fn parse_past_date(text: &str) -> Result<DateTime<Utc>, Box<dyn std::error::Error>> {
+    parse_at(text, now(), true)
+}
+
+/// Parses a date relative to `now`:
+/// - `2026-11-03`, `2026-11-03 14:00` or an RFC 3339 timestamp
+/// - `today`, `tomorrow`, `yesterday`, `now`
+/// - a weekday (`friday`, `fri`, `next friday`): the first one after today;
+///   `last friday` is the latest one before today
+/// - `next week` (Monday), `next month`, `next year` (their first day)
+/// - `eow`, `eom`, `eoy`: the last day of the current week, month or year
+/// - `in 3 days`, `in 2w`, `3 hours ago` (minutes, hours, days, weeks, months, years)
+///
+/// Any of these but the timestamps may end with a time (`tomorrow 14:00`,
+/// `friday at 9am`). Words may also be joined with `-` or `_` (`next-friday`), as
+/// in filters. Dates without a time are stored as local midnight.
+pub fn parse_human_date_at(text: &str, now: DateTime<Local>) -> Result<DateTime<Utc>, Box<dyn std::error::Error>> {
+    parse_at(text, now, false)
+}
+
+fn parse_at(text: &str, now: DateTime<Local>, past: bool) -> Result<DateTime<Utc>, Box<dyn std::error::Error>> {
+    let invalid = || format!("Invalid date '{}', expected e.g. 2026-11-03, tomorrow, next friday or in 3 days", text);
+    let text = text.trim();
+    if let Some(timestamp) = absolute(text) {
+        return Ok(timestamp);
+    }
+
+    let lower = text.to_lowercase();
+    let mut words: Vec<&str> = lower
+        .split(|c: char| c.is_whitespace() || c == '-' || c == '_')
+        .filter(|w| !w.is_empty())
+        .collect();
+    let time = match words.last().and_then(|w| parse_time(w)) {
+        Some(time) => {
+            words.pop();
+            if words.last() == Some(&"at") {
+                words.pop();
+            }
+            Some(time)
+        }
+        None => None,
+    };
+
+    let now = now.naive_local();
+    let moment = match (relative(&words, now, past), time) {
+        (Some(moment), Some(time)) => moment.date().and_time(time),
+        (Some(moment), None) => moment,
+        (None, Some(time)) if words.is_empty() => now.date().and_time(time),
+        _ => return Err(invalid().into()),
+    };
+    Ok(from_local(moment))
+}
+
+/// The last second of the day when `timestamp` is a date without a time (local
+/// midnight), otherwise `timestamp` itself; used to make date bounds inclusive.
+pub fn end_of_day(timestamp: DateTime<Utc>) -> DateTime<Utc> {
+    let local = timestamp.with_timezone(&Local);
+    if local.time() != NaiveTime::MIN {
+        return timestamp;
+    }
+    from_local(local.date_naive().and_time(NaiveTime::MIN) + Duration::days(1)) - Duration::seconds(1)
+}
+
+/// Parses a duration such as `1h30m`, `45m`, `2h`, `1.5h`, `90min` or `1:30`.
+pub fn parse_duration(text: &str) -> Result<Duration, Box<dyn std::error::Error>> {
+    let invalid = || format!("Invalid duration '{}', expected e.g. 1h30m, 45m or 1.5h", text);
+    let text = text.trim().to_lowercase();
+    if let Some((hours, minutes)) = text.split_once(':') {
+        let hours: u32 = hours.parse().map_err(|_| invalid())?;
+        let minutes: u32 = minutes.parse().map_err(|_| invalid())?;
+        if minutes >= 60 || hours == 0 && minutes == 0 {
+            return Err(invalid().into());
+        }
+        return Duration::try_minutes(i64::from(hours) * 60 + i64::from(minutes)).ok_or_else(|| invalid().into());
+    }
+
+    let mut total = 0.0;
+    let mut rest = text.as_str();
+    while !rest.is_empty() {
+        let number_end = rest.find(|c: char| !c.is_ascii_digit() && c != '.').ok_or_else(invalid)?;
+        let unit_end = rest[number_end..].find(|c: char| c.is_ascii_digit()).map_or(rest.len(), |i| number_end + i);
+        let amount: f64 = rest[..number_end].parse().map_err(|_| invalid())?;
+        let minutes = match rest[number_end..unit_end].trim() {
+            "m" | "min" | "mins" | "minute" | "minutes" => 1.0,
+            "h" | "hr" | "hrs" | "hour" | "hours" => 60.0,
+            "d" | "day" | "days" => 24.0 * 60.0,
+            _ => return Err(invalid().into()),
+        };
+        total += amount * minutes;
+        rest = rest[unit_end..].trim_start();
+    }
+    // `as` saturates, so out-of-range totals are caught by `try_minutes`.
+    if !total.is_finite() || total <= 0.0 {
+        return Err(invalid().into());
+    }
+    Duration::try_minutes(total.round() as i64).filter(|d| *d > Duration::zero()).ok_or_else(|| invalid().into())
+}
+
+/// Formats a duration in hours and minutes, e.g. `1h30m`, `45m` or `2h`.
+pub fn format_duration(duration: Duration) -> String {
+    let minutes = duration.num_minutes();
+    match (minutes / 60, minutes % 60) {
+        (0, minutes) => format!("{}m", minutes),
+        (hours, 0) => format!("{}h", hours),
+        (hours, minutes) => format!("{}h{:02}m", hours, minutes),
+    }
+}
+
+/// Timestamps written out in full: an ISO date with an optional time, or RFC 3339.
+fn absolute(text: &str) -> Option<DateTime<Utc>> {
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(text) {
+        return Some(timestamp.with_timezone(&Utc));
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(text, "%Y-%m-%d") {
+        return Some(from_local(date.and_time(NaiveTime::MIN)));
+    }
+    ["%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(text, format).ok())
+        .map(from_local)
+}
+
+/// Resolves the words of a relative date. Results naming a day are at midnight.
+/// In the `past`, a bare weekday is the latest one up to today.
+fn relative(words: &[&str], now: NaiveDateTime, past: bool) -> Option<NaiveDateTime> {
+    let today = now.date();
+    let day = |date: NaiveDate| Some(date.and_time(NaiveTime::MIN));
+    match words {
+        ["now"] => Some(to_minute(now)),
+        ["today"] => day(today),
+        ["tomorrow"] => day(today + Duration::days(1)),
+        ["yesterday"] => day(today - Duration::days(1)),
+        ["next", "week"] => day(next_weekday(today, Weekday::Mon)),
+        ["next", "month"] => day(today.with_day(1)?.checked_add_months(Months::new(1))?),
+        ["next", "year"] => day(NaiveDate::from_ymd_opt(today.year() + 1, 1, 1)?),
+        ["eow"] => day(today + Duration::days(6 - i64::from(today.weekday().num_days_from_monday()))),
+        ["eom"] => day(today.with_day(1)?.checked_add_months(Months::new(1))? - Duration::days(1)),
+        ["eoy"] => day(NaiveDate::from_ymd_opt(today.year(), 12, 31)?),
+        ["last", weekday] => day(previous_weekday(today, weekday.parse().ok()?, false)),
+        [weekday] if past => day(previous_weekday(today, weekday.parse().ok()?, true)),
+        [weekday] | ["next", weekday] => day(next_weekday(today, weekday.parse().ok()?)),
+        ["in", amount @ ..] => shift(now, amount, 1),
+        [amount @ .., "ago"] => shift(now, amount, -1),
+        _ => None,
+    }
+}
+
+/// Moves `now` by an amount such as ["3", "days"] or ["3d"] in `direction`.
+/// Whole days and longer give a date; minutes and hours keep the time.
+fn shift(now: NaiveDateTime, amount: &[&str], direction: i64) -> Option<NaiveDateTime> {
+    let (count, unit) = match amount {
+        [count, unit] => (count.parse::<i64>().ok()?, *unit),
+        [compact] => {
+            let digits = compact.find(|c: char| !c.is_ascii_digit())?;
+            (compact[..digits].parse::<i64>().ok()?, &compact[digits..])
+        }
+        _ => return None,
+    };
+    let count = count.checked_mul(direction)?;
+    let months = |n: i64| {
+        let date = now.date();
+        let shifted = if n >= 0 {
+            date.checked_add_months(Months::new(u32::try_from(n).ok()?))
+        } else {
+            date.checked_sub_months(Months::new(u32::try_from(-n).ok()?))
+        };
+        shifted.map(|d| d.and_time(NaiveTime::MIN))
+    };
+    // Amounts beyond the calendar give `None`, so they are reported as invalid dates.
+    let days = |n: i64| Some(now.date().checked_add_signed(Duration::try_days(n)?)?.and_time(NaiveTime::MIN));
+    match unit {
+        "min" | "mins" | "minute" | "minutes" => to_minute(now).checked_add_signed(Duration::try_minutes(count)?),
+        "h" | "hour" | "hours" => to_minute(now).checked_add_signed(Duration::try_hours(count)?),
+        "d" | "day" | "days" => days(count),
+        "w" | "week" | "weeks" => days(count.checked_mul(7)?),
+        "month" | "months" => months(count),
+        "y" | "year" | "years" => months(count.checked_mul(12)?),
+        _ => None,
+    }
+}
+
+/// The first `weekday` after `today`.
+fn next_weekday(today: NaiveDate, weekday: Weekday) -> NaiveDate {
+    let ahead = (7 + weekday.num_days_from_monday() - today.weekday().num_days_from_monday()) % 7;
+    today + Duration::days(if ahead == 0 { 7 } else { i64::from(ahead) })
+}
+
+/// The latest `weekday` before `today`, or up to it when `including_today`.
+fn previous_weekday(today: NaiveDate, weekday: Weekday, including_today: bool) -> NaiveDate {
+    let back = (7 + today.weekday().num_days_from_monday() - weekday.num_days_from_monday()) % 7;
+    today - Duration::days(if back == 0 && !including_today { 7 } else { i64::from(back) })
+}
+
+/// A time of day: `14:00`, `9:30`, `9am` or `5:15pm`.
+fn parse_time(word: &str) -> Option<NaiveTime> {
+    let (clock, offset) = if let Some(clock) = word.strip_suffix("am") {
+        (clock, Some(0))
+    } else if let Some(clock) = word.strip_suffix("pm") {
+        (clock, Some(12))
+    } else {
+        (word, None)
+    };
+    let (hour, minute) = match clock.split_once(':') {
+        Some((hour, minute)) => (hour.parse::<u32>().ok()?, minute.parse::<u32>().ok()?),
+        // A bare number is a count ("in 3 days"), not an hour, unless it says am/pm.
+        None if offset.is_some() => (clock.parse::<u32>().ok()?, 0),
+        None => return None,
+    };
+    let hour = match offset {
+        Some(_) if !(1..=12).contains(&hour) => return None,
+        Some(offset) => hour % 12 + offset,
+        None => hour,
+    };
+    NaiveTime::from_hms_opt(hour, minute, 0)
+}
+
+/// Converts a local date and time to a stored timestamp.
+fn from_local(local: NaiveDateTime) -> DateTime<Utc> {
+    Local
+        .from_local_datetime(&local)
+        .earliest()
+        .map(|timestamp| timestamp.with_timezone(&Utc))
+        .unwrap_or_else(|| local.and_utc())
+}
+
+/// `moment` without its seconds.
+fn to_minute(moment: NaiveDateTime) -> NaiveDateTime {
+    moment.with_second(0).and_then(|m| m.with_nanosecond(0)).unwrap_or(moment)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Friday 2026-10-16, 09:30 local time.
+    fn friday() -> DateTime<Local> {
+        Local.with_ymd_and_hms(2026, 10, 16, 9, 30, 0).unwrap()
+    }
+
+    /// Parses `text` relative to `now` and returns the local date and time.
+    fn parse(text: &str, now: DateTime<Local>) -> NaiveDateTime {
+        parse_human_date_at(text, now).unwrap().with_timezone(&Local).naive_local()
+    }
+
+    fn day(year: i32, month: u32, day: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap().and_time(NaiveTime::MIN)
+    }
+
+    #[test]
+    fn weekday_rolls_over_to_next_week() {
+        assert_eq!(parse("friday", friday()), day(2026, 10, 23));
+        assert_eq!(parse("next friday", friday()), day(2026, 10, 23));
+        assert_eq!(parse("mon", friday()), day(2026, 10, 19));
+        assert_eq!(parse("thursday", friday()), day(2026, 10, 22));
+    }
+
+    #[test]
+    fn last_weekday_on_that_weekday_is_a_week_back() {
+        assert_eq!(parse("last friday", friday()), day(2026, 10, 9));
+        assert_eq!(parse("last thursday", friday()), day(2026, 10, 15));
+    }
+
+    #[test]
+    fn past_weekday_includes_today() {
+        let parsed = parse_at("friday", friday(), true).unwrap().with_timezone(&Local).naive_local();
+        assert_eq!(parsed, day(2026, 10, 16));
+    }
+
+    #[test]
+    fn end_of_month() {
+        assert_eq!(parse("eom", friday()), day(2026, 10, 31));
+        let december = Local.with_ymd_and_hms(2026, 12, 15, 9, 30, 0).unwrap();
+        assert_eq!(parse("eom", december), day(2026, 12, 31));
+        let february = Local.with_ymd_and_hms(2028, 2, 10, 9, 30, 0).unwrap();
+        assert_eq!(parse("eom", february), day(2028, 2, 29));
+    }
+
+    #[test]
+    fn in_two_weeks() {
+        assert_eq!(parse("in 2w", friday()), day(2026, 10, 30));
+        assert_eq!(parse("in 2 weeks", friday()), day(2026, 10, 30));
+        assert_eq!(parse("2 weeks ago", friday()), day(2026, 10, 2));
+    }
+
+    #[test]
+    fn times_of_day() {
+        let quarter_past_five = NaiveTime::from_hms_opt(17, 15, 0).unwrap();
+        assert_eq!(parse("5:15pm", friday()), day(2026, 10, 16).date().and_time(quarter_past_five));
+        assert_eq!(parse("tomorrow at 5:15pm", friday()), day(2026, 10, 17).date().and_time(quarter_past_five));
+        assert_eq!(parse("next-monday 9am", friday()), day(2026, 10, 19) + Duration::hours(9));
+        assert!(parse_human_date_at("13pm", friday()).is_err());
+    }
+
+    #[test]
+    fn rejects_amounts_beyond_the_calendar() {
+        assert!(parse_human_date_at("in 99999999999 days", friday()).is_err());
+        assert!(parse_human_date_at("99999999999 weeks ago", friday()).is_err());
+        assert!(parse_human_date_at("in 9999999999999 hours", friday()).is_err());
+        assert!(parse_human_date_at("in 999999999999999 minutes", friday()).is_err());
+        assert!(parse_human_date_at("in 99999999999 years", friday()).is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_words() {
+        assert!(parse_human_date_at("someday", friday()).is_err());
+        assert!(parse_human_date_at("in 3 fortnights", friday()).is_err());
+    }
+}
@@ -0,0 +1,119 @@
+use chrono::{DateTime, Utc};
+use crate::models::Task;
+use crate::models::dates::{end_of_day, parse_human_date};
+
+/// A single condition of a `--where` expression.
+#[derive(Debug, Clone)]
+enum Condition {
+    /// `tag:<name>` — the task carries the tag
+    Tag(String),
+    /// `done:<yes|no>` — the task's completion state
+    Done(bool),
+    /// `title:<text>` — case-insensitive substring of the title
+    Title(String),
+    /// `project:<name>` — the task belongs to the project (list), ignoring case
+    Project(String),
+    /// `priority:<letter>` — the task has that priority
+    Priority(char),
+    /// Bare word — case-insensitive substring of the title or description
+    Text(String),
+    /// `due.before:<date>` / `scheduled.before:<date>` — set, and on or before the
+    /// date (a date without a time covers the whole day)
+    Before(DateField, DateTime<Utc>),
+    /// `due.after:<date>` / `scheduled.after:<date>` — set, and on or after the date
+    After(DateField, DateTime<Utc>),
+}
+
+/// The task dates that can be compared in a filter.
+#[derive(Debug, Clone, Copy)]
+enum DateField {
+    Due,
+    Scheduled,
+}
+
+impl DateField {
+    fn of(self, task: &Task) -> Option<DateTime<Utc>> {
+        match self {
+            DateField::Due => task.due,
+            DateField::Scheduled => task.scheduled,
+        }
+    }
+}
+
+/// A filter built from a `--where` expression such as `tag:sprint-4 done:no`.
+/// Terms are separated by whitespace and must all match; prefixing a term with
+/// `-` negates it (e.g. `-tag:blocked`). Dates are parsed like `--due`, with `-`
+/// joining words (e.g. `due.before:next-friday`).
+#[derive(Debug, Clone, Default)]
+pub struct TaskFilter {
+    conditions: Vec<(bool, Condition)>,
+}
+
+impl TaskFilter {
+    /// Parses a `--where` expression, returning an error for unknown keys or bad values.
+    pub fn parse(expression: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut conditions = Vec::new();
+        for term in expression.split_whitespace() {
+            let (negated, term) = match term.strip_prefix('-') {
+                Some(rest) if !rest.is_empty() => (true, rest),
+                _ => (false, term),
+            };
+            let condition = match term.split_once(':') {
+                Some(("tag", value)) => Condition::Tag(value.to_string()),
+                Some(("done", value)) => Condition::Done(parse_bool(value)?),
+                Some(("title", value)) => Condition::Title(value.to_lowercase()),
+                Some(("project", value)) => Condition::Project(value.to_string()),
+                Some(("priority", value)) => Condition::Priority(parse_priority(value)?),
+                Some(("due.before", value)) => Condition::Before(DateField::Due, end_of_day(parse_human_date(value)?)),
+                Some(("due.after", value)) => Condition::After(DateField::Due, parse_human_date(value)?),
+                Some(("scheduled.before", value)) => {
+                    Condition::Before(DateField::Scheduled, end_of_day(parse_human_date(value)?))
+                }
+                Some(("scheduled.after", value)) => Condition::After(DateField::Scheduled, parse_human_date(value)?),
+                Some((key, _)) => return Err(format!("Unknown filter key '{}'", key).into()),
+                None => Condition::Text(term.to_lowercase()),
+            };
+            conditions.push((negated, condition));
+        }
+        if conditions.is_empty() {
+            return Err("Filter expression is empty".into());
+        }
+        Ok(TaskFilter { conditions })
+    }
+
+    /// Returns true when the task satisfies every condition of the filter.
+    pub fn matches(&self, task: &Task) -> bool {
+        self.conditions.iter().all(|(negated, condition)| {
+            let hit = match condition {
+                Condition::Tag(tag) => task.has_tag(tag),
+                Condition::Done(done) => task.done == *done,
+                Condition::Title(text) => task.title.to_lowercase().contains(text),
+                Condition::Project(project) => task.project.as_ref().is_some_and(|p| p.eq_ignore_ascii_case(project)),
+                Condition::Priority(priority) => task.priority == Some(*priority),
+                Condition::Text(text) => {
+                    task.title.to_lowercase().contains(text)
+                        || task.description.to_lowercase().contains(text)
+                }
+                Condition::Before(field, limit) => field.of(task).is_some_and(|date| date <= *limit),
+                Condition::After(field, limit) => field.of(task).is_some_and(|date| date >= *limit),
+            };
+            hit != *negated
+        })
+    }
+}
+
+fn parse_priority(value: &str) -> Result<char, Box<dyn std::error::Error>> {
+    let mut chars = value.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if c.is_ascii_alphabetic() => Ok(c.to_ascii_uppercase()),
+        _ => Err(format!("Expected a priority letter, got '{}'", value).into()),
+    }
+}
+
+fn parse_bool(value: &str) -> Result<bool, Box<dyn std::error::Error>> {
+    match value.to_lowercase().as_str() {
+        "yes" | "true" | "y" | "1" => Ok(true),
+        "no" | "false" | "n" | "0" => Ok(false),
+        _ => Err(format!("Expected yes/no, got '{}'", value).into()),
+    }
+}
@@ -0,0 +1,155 @@
+use std::fs::{self, OpenOptions}; // Filesystem operations
+use std::io::Write; // Appending lines to the log file
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Utc}; // Timestamps for each change
+use serde::{Serialize, Deserialize};
+use serde_json::{Map, Value}; // Field values are stored as raw JSON
+use crate::models::Task;
+use crate::models::dates::now; // The current time, or TODO_NOW
+
+/// The kind of mutation that produced a history entry.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    Created,
+    Updated,
+    Deleted,
+}
+
+impl std::fmt::Display for ChangeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            ChangeKind::Created => "created",
+            ChangeKind::Updated => "updated",
+            ChangeKind::Deleted => "deleted",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+/// A single field that changed, with its value before and after the mutation.
+/// - `old` is `null` when the task was just created
+/// - `new` is `null` when the task was deleted
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FieldChange {
+    pub field: String,
+    pub old: Value,
+    pub new: Value,
+}
+
+/// One record in the audit log: who changed which task, when, and how.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ChangeEntry {
+    pub task_id: u32,
+    pub kind: ChangeKind,
+    pub author: String,
+    pub timestamp: DateTime<Utc>,
+    pub changes: Vec<FieldChange>,
+}
+
+impl ChangeEntry {
+    /// Builds an entry describing the transition `old` → `new` of a single task.
+    /// Returns `None` when nothing actually changed.
+    pub fn between(old: Option<&Task>, new: Option<&Task>) -> Option<ChangeEntry> {
+        let (task_id, kind) = match (old, new) {
+            (None, Some(task)) => (task.id, ChangeKind::Created),
+            (Some(task), None) => (task.id, ChangeKind::Deleted),
+            (Some(task), Some(_)) => (task.id, ChangeKind::Updated),
+            (None, None) => return None,
+        };
+
+        let changes = diff_fields(old, new);
+        if changes.is_empty() {
+            return None;
+        }
+
+        Some(ChangeEntry {
+            task_id,
+            kind,
+            author: current_author(),
+            timestamp: now().with_timezone(&Utc),
+            changes,
+        })
+    }
+}
+
+/// Compares two versions of a task field by field, using their JSON representation
+/// so that every serialized field is covered without listing them by hand.
+fn diff_fields(old: Option<&Task>, new: Option<&Task>) -> Vec<FieldChange> {
+    let old_fields = to_fields(old);
+    let new_fields = to_fields(new);
+
+    let mut names: Vec<&String> = old_fields.keys().chain(new_fields.keys()).collect();
+    names.sort();
+    names.dedup();
+    // The ID is already recorded on the entry itself.
+    names.retain(|name| name.as_str() != "id");
+
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let before = old_fields.get(name).cloned().unwrap_or(Value::Null);
+            let after = new_fields.get(name).cloned().unwrap_or(Value::Null);
+            (before != after).then(|| FieldChange { field: name.clone(), old: before, new: after })
+        })
+        .collect()
+}
+
+fn to_fields(task: Option<&Task>) -> Map<String, Value> {
+    match task.map(serde_json::to_value) {
+        Some(Ok(Value::Object(map))) => map,
+        _ => Map::new(),
+    }
+}
+
+/// Identifies the person making a change: `TODO_AUTHOR` if set, otherwise the OS user.
+pub fn current_author() -> String {
+    ["TODO_AUTHOR", "USER", "USERNAME"]
+        .iter()
+        .find_map(|key| std::env::var(key).ok().filter(|v| !v.is_empty()))
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Append-only audit log stored as JSON Lines next to the tasks file
+/// (e.g. `data/todos.json` → `data/todos.history.jsonl`).
+pub struct HistoryLog {
+    path: PathBuf,
+}
+
+impl HistoryLog {
+    /// Derives the log location from the path of the tasks JSON file.
+    pub fn for_store(json_path: &str) -> Self {
+        HistoryLog {
+            path: Path::new(json_path).with_extension("history.jsonl"),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Appends entries to the end of the log; existing lines are never rewritten.
+    pub fn append(&self, entries: &[ChangeEntry]) -> Result<(), Box<dyn std::error::Error>> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        for entry in entries {
+            writeln!(file, "{}", serde_json::to_string(entry)?)?;
+        }
+        Ok(())
+    }
+
+    /// Reads every entry in the log, oldest first. A missing log is treated as empty.
+    pub fn entries(&self) -> Result<Vec<ChangeEntry>, Box<dyn std::error::Error>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let data = fs::read_to_string(&self.path)?;
+        let mut entries = Vec::new();
+        for line in data.lines().filter(|l| !l.trim().is_empty()) {
+            entries.push(serde_json::from_str(line)?);
+        }
+        Ok(entries)
+    }
+}
@@ -0,0 +1,263 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+use serde_json::{Map, Value};
+use crate::models::{ChangeEntry, Task};
+use crate::models::task::derived_uuid;
+
+/// Result of merging two diverged versions of the task list.
+/// - `tasks`: The merged list, ordered by ID
+/// - `conflicts`: One line per genuine conflict (the same field changed
+///   differently on both sides, or an edit against a deletion), saying what was kept
+/// - `renumbered`: Tasks created on both sides under the same ID, as
+///   (ID on their side, new ID); the local task keeps the original ID
+pub struct MergeOutcome {
+    pub tasks: Vec<Task>,
+    pub conflicts: Vec<String>,
+    pub renumbered: Vec<(u32, u32)>,
+}
+
+/// Three-way merge of task lists by task ID, against their common ancestor `base`.
+/// - A task changed (or created, or deleted) on one side only takes that side's version.
+/// - When both sides changed the same task, fields are merged one by one: each takes
+///   the side that changed it, and lists (tags, notes) combine additions and removals
+///   from both. A field both sides set to different values keeps the local value.
+/// - An edit wins over a deletion.
+/// - Different tasks created on both sides under the same ID (told apart by their
+///   UUIDs) are both kept: theirs gets a fresh ID, and their subtasks follow.
+///   Fresh IDs are above `last_issued`, the highest ID ever given to a task (see
+///   `TaskManager::last_issued_id`), so IDs of deleted tasks are not re-issued.
+pub fn merge_tasks(base: &[Task], ours: &[Task], theirs: &[Task], last_issued: u32) -> MergeOutcome {
+    let (base, ours, theirs) = (with_uuids(base), with_uuids(ours), with_uuids(theirs));
+    let (theirs, renumbered) = renumber_collisions(&base, &ours, &theirs, last_issued);
+    let index = |tasks: &[Task]| -> HashMap<u32, Task> { tasks.iter().map(|t| (t.id, t.clone())).collect() };
+    let (base, ours, theirs) = (index(&base), index(&ours), index(&theirs));
+    let ids: BTreeSet<u32> = base.keys().chain(ours.keys()).chain(theirs.keys()).copied().collect();
+
+    let mut tasks = Vec::new();
+    let mut conflicts = Vec::new();
+    for id in ids {
+        let (b, o, t) = (base.get(&id), ours.get(&id), theirs.get(&id));
+        if same(o, t) || same(b, t) {
+            tasks.extend(o.cloned());
+        } else if same(b, o) {
+            tasks.extend(t.cloned());
+        } else {
+            match (b, o, t) {
+                (Some(b), Some(o), Some(t)) => tasks.push(merge_fields(b, o, t, &mut conflicts)),
+                // The same task reached both sides by other routes, then changed.
+                (None, Some(o), Some(t)) => tasks.push(merge_fields(&Task::default(), o, t, &mut conflicts)),
+                (_, None, Some(t)) => {
+                    conflicts.push(format!("Task {} was deleted locally but edited remotely; kept the edit", id));
+                    tasks.push(t.clone());
+                }
+                (_, Some(o), _) => {
+                    conflicts.push(format!("Task {} was deleted remotely but edited locally; kept the edit", id));
+                    tasks.push(o.clone());
+                }
+                (_, None, None) => {}
+            }
+        }
+    }
+    MergeOutcome { tasks, conflicts, renumbered }
+}
+
+/// Merges two versions of the append-only history log: every entry of either side,
+/// once, ordered by time.
+pub fn merge_history(ours: &str, theirs: &str) -> String {
+    let mut seen = HashSet::new();
+    let mut lines: Vec<&str> = ours
+        .lines()
+        .chain(theirs.lines())
+        .filter(|l| !l.trim().is_empty() && seen.insert(*l))
+        .collect();
+    // Sorting is stable, so lines we cannot read stay next to their neighbours.
+    lines.sort_by_key(|line| serde_json::from_str::<ChangeEntry>(line).ok().map(|e| e.timestamp));
+
+    let mut merged = lines.join("\n");
+    if !merged.is_empty() {
+        merged.push('\n');
+    }
+    merged
+}
+
+/// The highest task ID named in a history log, i.e. the highest ever issued by
+/// the copies whose changes it records.
+pub fn last_issued_in(history: &str) -> u32 {
+    history
+        .lines()
+        .filter_map(|line| serde_json::from_str::<ChangeEntry>(line).ok())
+        .map(|entry| entry.task_id)
+        .max()
+        .unwrap_or(0)
+}
+
+/// Files written before tasks had UUIDs get the ones `TaskManager` would derive,
+/// so both sides agree on them.
+fn with_uuids(tasks: &[Task]) -> Vec<Task> {
+    tasks
+        .iter()
+        .map(|task| Task {
+            uuid: if task.uuid.is_nil() { derived_uuid(task.id) } else { task.uuid },
+            ..task.clone()
+        })
+        .collect()
+}
+
+/// Gives the tasks both sides created under the same ID fresh IDs on their side,
+/// and points their side's `parent` links at them. They are different tasks when
+/// their UUIDs differ, or, for UUIDs derived from that very ID by an upgrade (on
+/// both sides), when their content does.
+fn renumber_collisions(base: &[Task], ours: &[Task], theirs: &[Task], last_issued: u32) -> (Vec<Task>, Vec<(u32, u32)>) {
+    let base_ids: HashSet<u32> = base.iter().map(|t| t.id).collect();
+    let mut next_id = base.iter().chain(ours).chain(theirs).map(|t| t.id).max().unwrap_or(0).max(last_issued) + 1;
+
+    let mut renumbered = Vec::new();
+    for task in theirs.iter().filter(|t| !base_ids.contains(&t.id)) {
+        let collides = |o: &Task| {
+            o.uuid != task.uuid || (o.uuid == derived_uuid(o.id) && !same(Some(o), Some(task)))
+        };
+        if ours.iter().any(|o| o.id == task.id && collides(o)) {
+            renumbered.push((task.id, next_id));
+            next_id += 1;
+        }
+    }
+    let new_id = |id: u32| renumbered.iter().find(|(old, _)| *old == id).map_or(id, |&(_, new)| new);
+
+    let theirs = theirs
+        .iter()
+        .map(|task| Task {
+            id: new_id(task.id),
+            parent: task.parent.map(new_id),
+            ..task.clone()
+        })
+        .collect();
+    (theirs, renumbered)
+}
+
+/// Field-by-field merge of a task both sides changed.
+fn merge_fields(base: &Task, ours: &Task, theirs: &Task, conflicts: &mut Vec<String>) -> Task {
+    let (b, o, t) = (to_map(base), to_map(ours), to_map(theirs));
+    let mut merged = Map::new();
+    for (field, ours_value) in &o {
+        let base_value = b.get(field).unwrap_or(&Value::Null);
+        let theirs_value = t.get(field).unwrap_or(&Value::Null);
+        let value = if ours_value == theirs_value || base_value == theirs_value {
+            ours_value.clone()
+        } else if base_value == ours_value {
+            theirs_value.clone()
+        } else if let (Value::Array(b), Value::Array(o), Value::Array(t)) = (base_value, ours_value, theirs_value) {
+            Value::Array(merge_lists(b, o, t))
+        } else {
+            conflicts.push(format!(
+                "Task {}: '{}' changed on both sides; kept the local value {}",
+                ours.id, field, ours_value
+            ));
+            ours_value.clone()
+        };
+        merged.insert(field.clone(), value);
+    }
+    serde_json::from_value(Value::Object(merged)).unwrap_or_else(|_| ours.clone())
+}
+
+/// Merges lists as sets: our items minus those they removed, then the items they added.
+fn merge_lists(base: &[Value], ours: &[Value], theirs: &[Value]) -> Vec<Value> {
+    let mut merged: Vec<Value> = ours
+        .iter()
+        .filter(|item| !base.contains(item) || theirs.contains(item))
+        .cloned()
+        .collect();
+    merged.extend(theirs.iter().filter(|item| !base.contains(item) && !ours.contains(item)).cloned());
+    merged
+}
+
+fn to_map(task: &Task) -> Map<String, Value> {
+    match serde_json::to_value(task) {
+        Ok(Value::Object(map)) => map,
+        _ => Map::new(),
+    }
+}
+
+/// Whether two versions of a task (or its absence) are identical.
+fn same(a: Option<&Task>, b: Option<&Task>) -> bool {
+    let value = |task: Option<&Task>| task.map(|t| serde_json::to_value(t).unwrap_or(Value::Null));
+    value(a) == value(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn task(id: u32, title: &str) -> Task {
+        Task { id, title: title.to_string(), uuid: derived_uuid(id), ..Task::default() }
+    }
+
+    fn find(outcome: &MergeOutcome, id: u32) -> &Task {
+        outcome.tasks.iter().find(|t| t.id == id).expect("task in the merge")
+    }
+
+    #[test]
+    fn independent_edits_are_combined() {
+        let base = vec![task(1, "Write report")];
+        let ours = vec![task(1, "Write the report")];
+        let theirs = vec![Task { done: true, ..task(1, "Write report") }];
+
+        let outcome = merge_tasks(&base, &ours, &theirs, 1);
+        assert!(outcome.conflicts.is_empty());
+        let merged = find(&outcome, 1);
+        assert_eq!(merged.title, "Write the report");
+        assert!(merged.done);
+    }
+
+    #[test]
+    fn field_changed_on_both_sides_keeps_ours() {
+        let base = vec![task(1, "Write report")];
+        let ours = vec![task(1, "Write the report")];
+        let theirs = vec![task(1, "Write a report")];
+
+        let outcome = merge_tasks(&base, &ours, &theirs, 1);
+        assert_eq!(outcome.conflicts.len(), 1);
+        assert!(outcome.conflicts[0].contains("'title'"));
+        assert_eq!(find(&outcome, 1).title, "Write the report");
+    }
+
+    #[test]
+    fn edit_wins_over_delete() {
+        let base = vec![task(1, "Write report"), task(2, "Call Bob")];
+        let ours = vec![task(2, "Call Bob")];
+        let theirs = vec![task(1, "Write the report"), task(2, "Call Bob")];
+
+        let outcome = merge_tasks(&base, &ours, &theirs, 2);
+        assert_eq!(outcome.conflicts.len(), 1);
+        assert!(outcome.conflicts[0].contains("deleted locally"));
+        assert_eq!(find(&outcome, 1).title, "Write the report");
+    }
+
+    #[test]
+    fn tag_lists_combine_both_sides() {
+        let tagged = |tags: &[&str]| Task { tags: tags.iter().map(|t| t.to_string()).collect(), ..task(1, "Ship") };
+        let base = vec![tagged(&["work", "urgent"])];
+        let ours = vec![tagged(&["urgent", "release"])];
+        let theirs = vec![tagged(&["work", "urgent", "q4"])];
+
+        let outcome = merge_tasks(&base, &ours, &theirs, 1);
+        assert!(outcome.conflicts.is_empty());
+        assert_eq!(find(&outcome, 1).tags, ["urgent", "release", "q4"]);
+    }
+
+    #[test]
+    fn tasks_created_on_both_sides_are_renumbered_above_issued_ids() {
+        let ours = vec![Task { uuid: Uuid::new_v4(), ..task(1, "Buy milk") }];
+        let theirs = vec![
+            Task { uuid: Uuid::new_v4(), ..task(1, "Book flights") },
+            Task { uuid: Uuid::new_v4(), parent: Some(1), ..task(2, "Pick seats") },
+        ];
+
+        // IDs up to 4 were issued before (e.g. to since-deleted tasks).
+        let outcome = merge_tasks(&[], &ours, &theirs, 4);
+        assert!(outcome.conflicts.is_empty());
+        assert_eq!(outcome.renumbered, [(1, 5)]);
+        assert_eq!(find(&outcome, 1).title, "Buy milk");
+        assert_eq!(find(&outcome, 5).title, "Book flights");
+        assert_eq!(find(&outcome, 2).parent, Some(5));
+    }
+}
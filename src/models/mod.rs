@@ -1,10 +1,17 @@
 pub mod task;
 pub mod task_manager;
+pub mod history;
+pub mod filter;
+pub mod repo;
+pub mod merge;
+pub mod dates;
 
 // Re-export for convenient use
-pub use task::Task;
+pub use task::{Note, Task, TimeEntry};
 pub use task_manager::TaskManager;
-pub use task::TaskStats;
+pub use task::{Breakdown, Effort, Estimate, TaskStats};
+pub use history::ChangeEntry;
+pub use filter::TaskFilter;
 
 // So that we can use the TaskManager in the main.rs file as:
 // >  use models::TaskManager;
@@ -0,0 +1,175 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command; // Versioning is delegated to the git binary
+use crate::models::Task;
+use crate::models::history::current_author;
+
+/// The data directory seen as a git repository, used to version the task files
+/// and share them through `sync`. Versioning is opt-in: nothing is committed
+/// until `sync --init` created the repository (e.g. `data/.git`).
+pub struct DataRepo {
+    dir: PathBuf,
+    /// File names, relative to `dir`, of the tasks JSON file and its history log.
+    tasks_file: String,
+    history_file: String,
+}
+
+impl DataRepo {
+    /// Uses the directory holding the tasks JSON file.
+    pub fn for_store(json_path: &str, history_path: &Path) -> Self {
+        let path = Path::new(json_path);
+        let file_name = |p: &Path| p.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+        DataRepo {
+            dir: path.parent().filter(|p| !p.as_os_str().is_empty()).unwrap_or(Path::new(".")).to_path_buf(),
+            tasks_file: file_name(path),
+            history_file: file_name(history_path),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.dir.join(".git").exists()
+    }
+
+    pub fn tasks_file(&self) -> &str {
+        &self.tasks_file
+    }
+
+    pub fn history_file(&self) -> &str {
+        &self.history_file
+    }
+
+    /// Path of a file in the data directory.
+    pub fn path_of(&self, file: &str) -> PathBuf {
+        self.dir.join(file)
+    }
+
+    /// Creates the repository and commits the current task files.
+    pub fn init(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.git(&["init", "-q"])?;
+        self.install_merge_driver()?;
+        self.commit_all("Start versioning tasks")?;
+        Ok(())
+    }
+
+    /// Lets git merge the tasks file with our "merge" subcommand and the history
+    /// log by keeping the lines of both sides. `.gitattributes` is committed with
+    /// the task files; the driver command is local git configuration. Git runs the
+    /// driver in the data directory, so it finds the history log by its file name.
+    pub fn install_merge_driver(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let attributes = format!("{} merge=todo\n{} merge=union\n", self.tasks_file, self.history_file);
+        fs::write(self.dir.join(".gitattributes"), attributes)?;
+
+        let exe = std::env::current_exe()?;
+        self.git(&["config", "merge.todo.name", "Task-level merge of the tasks file"])?;
+        let driver = format!("'{}' merge %O %A %B --history '{}'", exe.display(), self.history_file);
+        self.git(&["config", "merge.todo.driver", &driver])?;
+        Ok(())
+    }
+
+    /// Points the `origin` remote at `url`, adding it if needed.
+    pub fn set_remote(&self, url: &str) -> Result<(), Box<dyn std::error::Error>> {
+        if self.succeeds(&["remote", "get-url", "origin"]) {
+            self.git(&["remote", "set-url", "origin", url])?;
+        } else {
+            self.git(&["remote", "add", "origin", url])?;
+        }
+        Ok(())
+    }
+
+    /// Commits the task files if they changed (or a merge is in progress).
+    /// Returns whether a commit was made.
+    pub fn commit_all(&self, message: &str) -> Result<bool, Box<dyn std::error::Error>> {
+        let mut add = vec!["add", "-A", "--"];
+        add.extend(
+            [self.tasks_file.as_str(), self.history_file.as_str(), ".gitattributes"]
+                .into_iter()
+                .filter(|f| self.dir.join(f).exists()),
+        );
+        self.git(&add)?;
+
+        let merging = self.dir.join(".git").join("MERGE_HEAD").exists();
+        if !merging && self.succeeds(&["diff", "--cached", "--quiet"]) {
+            return Ok(false);
+        }
+        self.git_as_author(&["commit", "-q", "-m", message])?;
+        Ok(true)
+    }
+
+    /// Contents of `file` at revision `rev`, or `None` if it did not exist there.
+    pub fn show(&self, rev: &str, file: &str) -> Result<Option<String>, Box<dyn std::error::Error>> {
+        if !self.succeeds(&["cat-file", "-e", &format!("{}:{}", rev, file)]) {
+            return Ok(None);
+        }
+        self.git(&["show", &format!("{}:{}", rev, file)]).map(Some)
+    }
+
+    /// Runs git in the data directory and returns its trimmed output, or its error
+    /// message when it fails.
+    pub fn git(&self, args: &[&str]) -> Result<String, Box<dyn std::error::Error>> {
+        let output = Command::new("git").arg("-C").arg(&self.dir).args(args).output()?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("git {}: {}", args.join(" "), stderr.trim()).into());
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim_end().to_string())
+    }
+
+    /// Runs a git command that creates commits (commit, merge), supplying an identity
+    /// on machines where git has no user configured yet.
+    pub fn git_as_author(&self, args: &[&str]) -> Result<String, Box<dyn std::error::Error>> {
+        let mut full = self.identity();
+        full.extend(args.iter().map(|a| a.to_string()));
+        self.git(&full.iter().map(String::as_str).collect::<Vec<_>>())
+    }
+
+    /// Runs git quietly and only reports whether it succeeded.
+    pub fn succeeds(&self, args: &[&str]) -> bool {
+        self.git(args).is_ok()
+    }
+
+    /// Identity flags falling back to the author recorded in the history log, or
+    /// nothing when git already knows the user.
+    fn identity(&self) -> Vec<String> {
+        let configured = |key: &str| self.git(&["config", key]).is_ok_and(|v| !v.is_empty());
+        if configured("user.name") && configured("user.email") {
+            return Vec::new();
+        }
+        let author = current_author();
+        vec![
+            "-c".to_string(),
+            format!("user.name={}", author),
+            "-c".to_string(),
+            format!("user.email={}@localhost", author),
+        ]
+    }
+}
+
+/// Describes a mutation as a commit message, e.g. "Update task 3: Buy milk" or
+/// "Delete tasks 4, 5".
+pub fn commit_message(transitions: &[(Option<Task>, Option<Task>)]) -> String {
+    let mut groups: Vec<(&str, Vec<&Task>)> = Vec::new();
+    for transition in transitions {
+        let (verb, task) = match transition {
+            (None, Some(new)) => ("Create", new),
+            (Some(_), Some(new)) => ("Update", new),
+            (Some(old), None) => ("Delete", old),
+            (None, None) => continue,
+        };
+        match groups.iter_mut().find(|(v, _)| *v == verb) {
+            Some((_, tasks)) => tasks.push(task),
+            None => groups.push((verb, vec![task])),
+        }
+    }
+
+    let parts: Vec<String> = groups
+        .iter()
+        .map(|(verb, tasks)| match tasks.as_slice() {
+            [task] => format!("{} task {}: {}", verb, task.id, task.title),
+            _ => {
+                let ids: Vec<String> = tasks.iter().map(|t| t.id.to_string()).collect();
+                format!("{} tasks {}", verb, ids.join(", "))
+            }
+        })
+        .collect();
+    parts.join("; ")
+}
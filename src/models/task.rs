@@ -1,27 +1,358 @@
-use serde::{Serialize, Deserialize};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+use crate::models::dates::{end_of_day, format_duration, parse_duration};
 
 /// Represents a single task record in the application.
-/// - `id`: Uniquely identifies each task
+/// - `id`: Short numeric handle used on the command line; never re-issued, even
+///   after the task is deleted
 /// - `title`: Brief name or label of the task
 /// - `description`: Detailed explanation of what needs to be done
+/// - `done`: Whether the task has been completed
+/// - `tags`: Free-form labels used to group and filter tasks
+/// - `priority`: Optional priority letter, `A` being the most important
+/// - `project`: Optional project (list) the task belongs to
+/// - `due`: Optional due date; a local midnight means "on that day"
+/// - `scheduled`: Optional date on which work should start
+/// - `parent`: ID of the task this one is a subtask of
+/// - `recurrence`: Optional iCalendar RRULE (e.g. `FREQ=WEEKLY;BYDAY=MO`), kept
+///   so recurring tasks survive a round trip through calendar apps
+/// - `created` / `completed`: When the task was created and last completed
+/// - `notes`: Timestamped annotations, oldest first
+/// - `source`: Where `scan` found the task as a code comment (`path:line`)
+/// - `uuid`: Globally unique identity, stable across copies of the store; used to
+///   tell tasks apart when syncing, merging and exporting
+/// - `time_entries`: Time worked on the task, oldest first; at most one timer
+///   (an entry without an end) runs across all tasks
+/// - `estimate`: Optional expected effort, as a duration or in story points
 ///
 /// It supports JSON serialization/deserialization via Serde, 
 /// making it easy to read/write tasks in the TaskManager.
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// Fields added after the first release default when missing from older JSON files.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct Task {
     pub id: u32,
     pub title: String,
+    #[serde(default)]
     pub description: String,
+    #[serde(default)]
+    pub done: bool,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub priority: Option<char>,
+    #[serde(default)]
+    pub project: Option<String>,
+    #[serde(default)]
+    pub parent: Option<u32>,
+    #[serde(default)]
+    pub due: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub scheduled: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub recurrence: Option<String>,
+    #[serde(default)]
+    pub created: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub completed: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub notes: Vec<Note>,
+    #[serde(default)]
+    pub source: Option<String>,
+    #[serde(default, deserialize_with = "nil_if_null")]
+    pub uuid: Uuid,
+    #[serde(default)]
+    pub time_entries: Vec<TimeEntry>,
+    #[serde(default)]
+    pub estimate: Option<Estimate>,
 }
 
-/// Stores basic statistics about the current set of tasks.
-/// - `total`: The total number of tasks currently stored
-/// - `last_id`: The highest task ID in use, allowing new tasks to be 
-///              assigned an incremented ID value
+/// A timestamped annotation on a task.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Note {
+    pub entry: DateTime<Utc>,
+    pub text: String,
+}
+
+/// Expected effort of a task, written as `1h30m` (a duration) or `3pt` (story
+/// points) on the command line and in the task file.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Estimate {
+    Duration(Duration),
+    Points(f64),
+}
+
+impl TryFrom<String> for Estimate {
+    type Error = String;
+
+    fn try_from(text: String) -> Result<Self, Self::Error> {
+        let lower = text.trim().to_lowercase();
+        let points = ["points", "point", "pts", "pt", "sp", "p"]
+            .iter()
+            .find_map(|unit| lower.strip_suffix(unit))
+            .and_then(|number| number.trim().parse::<f64>().ok());
+        match points {
+            Some(points) if !points.is_finite() => Err(format!("Invalid estimate '{}': points must be a number", text)),
+            Some(points) if points < 0.0 => Err(format!("Invalid estimate '{}': points cannot be negative", text)),
+            Some(points) => Ok(Estimate::Points(points)),
+            None => parse_duration(&text)
+                .map(Estimate::Duration)
+                .map_err(|_| format!("Invalid estimate '{}', expected a duration (2h, 1h30m) or points (3pt)", text)),
+        }
+    }
+}
+
+impl From<Estimate> for String {
+    fn from(estimate: Estimate) -> String {
+        match estimate {
+            Estimate::Duration(duration) => format_duration(duration),
+            Estimate::Points(points) => format!("{}pt", points),
+        }
+    }
+}
+
+/// Totals of the estimates of a set of tasks.
+/// - `estimated`: Sum of the duration estimates
+/// - `points`: Sum of the story point estimates
+/// - `tracked`: Time tracked on the tasks estimated as a duration
+/// - `remaining`: What is left of the duration estimates of open tasks once their
+///   tracked time is taken off (never below zero per task)
+#[derive(Debug, Clone, Copy)]
+pub struct Effort {
+    pub estimated: Duration,
+    pub points: f64,
+    pub tracked: Duration,
+    pub remaining: Duration,
+}
+
+impl Effort {
+    /// Adds up the estimates of `tasks`, counting running timers up to `now`.
+    pub fn of<'a>(tasks: impl IntoIterator<Item = &'a Task>, now: DateTime<Utc>) -> Effort {
+        let mut effort = Effort {
+            estimated: Duration::zero(),
+            points: 0.0,
+            tracked: Duration::zero(),
+            remaining: Duration::zero(),
+        };
+        for task in tasks {
+            match task.estimate {
+                Some(Estimate::Duration(estimate)) => {
+                    let tracked = task.time_tracked(now);
+                    effort.estimated += estimate;
+                    effort.tracked += tracked;
+                    if !task.done {
+                        effort.remaining += (estimate - tracked).max(Duration::zero());
+                    }
+                }
+                Some(Estimate::Points(points)) => effort.points += points,
+                None => {}
+            }
+        }
+        effort
+    }
+}
+
+/// A stretch of time worked on a task; `end` is `None` while its timer runs.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TimeEntry {
+    pub start: DateTime<Utc>,
+    pub end: Option<DateTime<Utc>>,
+}
+
+impl TimeEntry {
+    /// The part of the entry between `from` and `until` (a running entry lasts
+    /// until `until`).
+    pub fn within(&self, from: Option<DateTime<Utc>>, until: DateTime<Utc>) -> Duration {
+        let start = from.map_or(self.start, |from| self.start.max(from));
+        let end = self.end.map_or(until, |end| end.min(until));
+        (end - start).max(Duration::zero())
+    }
+}
+
+impl Task {
+    /// Adds a tag unless the task already carries it.
+    pub fn add_tag(&mut self, tag: &str) {
+        if !self.has_tag(tag) {
+            self.tags.push(tag.to_string());
+        }
+    }
+
+    /// Removes a tag if present.
+    pub fn remove_tag(&mut self, tag: &str) {
+        self.tags.retain(|t| !t.eq_ignore_ascii_case(tag));
+    }
+
+    /// When the task's timer was started, if it is running.
+    pub fn running_since(&self) -> Option<DateTime<Utc>> {
+        self.time_entries.iter().find(|e| e.end.is_none()).map(|e| e.start)
+    }
+
+    /// All time tracked on the task, counting a running timer up to `now`.
+    pub fn time_tracked(&self, now: DateTime<Utc>) -> Duration {
+        self.time_entries.iter().map(|e| e.within(None, now)).sum()
+    }
+
+    /// Tags are compared case-insensitively.
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+
+    /// Returns a copy with the fields of a JSON object (as sent to the HTTP API or
+    /// over JSON-RPC) laid over this task. The ID and UUID are kept, unknown fields are
+    /// rejected, and the title must not end up empty.
+    pub fn patched(&self, patch: &Value) -> Result<Task, String> {
+        let mut fields = match serde_json::to_value(self) {
+            Ok(Value::Object(map)) => map,
+            _ => return Err("Task did not serialize to an object".to_string()),
+        };
+        for (key, value) in patch.as_object().into_iter().flatten() {
+            if key == "id" || key == "uuid" {
+                continue;
+            }
+            if !fields.contains_key(key) {
+                return Err(format!("Unknown field '{}'", key));
+            }
+            fields.insert(key.clone(), value.clone());
+        }
+
+        let patched: Task = serde_json::from_value(Value::Object(fields)).map_err(|e| format!("Invalid task: {}", e))?;
+        if patched.title.trim().is_empty() {
+            return Err("The title cannot be empty".to_string());
+        }
+        Ok(Task { id: self.id, uuid: self.uuid, ..patched })
+    }
+}
+
+/// The UUID given to tasks stored before every task had one. It is derived from
+/// the ID, so every copy of the store (and earlier Taskwarrior exports) agrees.
+pub fn derived_uuid(id: u32) -> Uuid {
+    Uuid::new_v5(&Uuid::NAMESPACE_URL, format!("todo-rust-cli:task/{}", id).as_bytes())
+}
+
+/// Reads a missing or `null` UUID as nil, to be backfilled by the TaskManager.
+fn nil_if_null<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Uuid, D::Error> {
+    Ok(Option::<Uuid>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+/// Stores statistics about a set of tasks.
+/// - `total`: The number of tasks
+/// - `last_id`: The highest task ID in use (IDs of deleted tasks may be higher;
+///   new IDs are also kept above those)
+/// - `open` / `done`: How many tasks are open and done
+/// - `overdue`: Open tasks whose due date has passed
+/// - `average_open_age`: Mean time since the open tasks were created, if any
+///   has a creation date
+/// - `completed_per_week`: Tasks completed in each of the last `THROUGHPUT_WEEKS`
+///   weeks, keyed by their Monday, oldest first
+/// - `by_tag` / `by_priority` / `by_project`: Open and done counts per value,
+///   sorted by name; a task with several tags counts for each
+/// - `effort`: Estimate totals of all tasks, compared with the time tracked
 ///
 /// The TaskManager uses `TaskStats` to summarize the tasks loaded from JSON.
 #[derive(Debug)]
 pub struct TaskStats {
     pub total: usize,
     pub last_id: u32,
-}
\ No newline at end of file
+    pub open: usize,
+    pub done: usize,
+    pub overdue: usize,
+    pub average_open_age: Option<Duration>,
+    pub completed_per_week: Vec<(NaiveDate, usize)>,
+    pub by_tag: Vec<Breakdown>,
+    pub by_priority: Vec<Breakdown>,
+    pub by_project: Vec<Breakdown>,
+    pub effort: Effort,
+}
+
+/// Open and done counts of the tasks sharing a tag, priority or project.
+#[derive(Debug, Clone)]
+pub struct Breakdown {
+    pub name: String,
+    pub open: usize,
+    pub done: usize,
+}
+
+impl TaskStats {
+    /// How many weeks `completed_per_week` covers, the current one included.
+    pub const THROUGHPUT_WEEKS: usize = 8;
+
+    /// Computes the statistics of `tasks` as of `now`.
+    pub fn of(tasks: &[Task], now: DateTime<Utc>) -> TaskStats {
+        let open: Vec<&Task> = tasks.iter().filter(|t| !t.done).collect();
+        let ages: Vec<Duration> = open.iter().filter_map(|t| t.created).map(|created| now - created).collect();
+        let average_open_age = match ages.len() {
+            0 => None,
+            count => Some(ages.iter().sum::<Duration>() / count as i32),
+        };
+
+        let today = now.with_timezone(&Local).date_naive();
+        let this_week = today - Duration::days(i64::from(today.weekday().num_days_from_monday()));
+        let completed_per_week = (0..Self::THROUGHPUT_WEEKS)
+            .rev()
+            .map(|weeks_ago| {
+                let monday = this_week - Duration::weeks(weeks_ago as i64);
+                let count = tasks
+                    .iter()
+                    .filter(|t| t.done)
+                    .filter_map(|t| t.completed)
+                    .map(|completed| completed.with_timezone(&Local).date_naive())
+                    .filter(|day| *day >= monday && *day < monday + Duration::weeks(1))
+                    .count();
+                (monday, count)
+            })
+            .collect();
+
+        TaskStats {
+            total: tasks.len(),
+            last_id: tasks.iter().map(|t| t.id).max().unwrap_or(0),
+            open: open.len(),
+            done: tasks.len() - open.len(),
+            overdue: open.iter().filter(|t| t.due.is_some_and(|due| end_of_day(due) < now)).count(),
+            average_open_age,
+            completed_per_week,
+            by_tag: Breakdown::of(tasks, |t| {
+                if t.tags.is_empty() { vec!["(no tag)".to_string()] } else { t.tags.clone() }
+            }),
+            by_priority: Breakdown::of(tasks, |t| {
+                vec![t.priority.map_or_else(|| "(no priority)".to_string(), |p| p.to_string())]
+            }),
+            by_project: Breakdown::of(tasks, |t| vec![t.project.clone().unwrap_or_else(|| "(no project)".to_string())]),
+            effort: Effort::of(tasks, now),
+        }
+    }
+
+    /// The share of tasks that are done, from 0 to 1 (0 without tasks).
+    pub fn completion_rate(&self) -> f64 {
+        match self.total {
+            0 => 0.0,
+            total => self.done as f64 / total as f64,
+        }
+    }
+}
+
+impl Breakdown {
+    /// Counts `tasks` under each of the names `names` gives them.
+    fn of(tasks: &[Task], names: impl Fn(&Task) -> Vec<String>) -> Vec<Breakdown> {
+        let mut breakdown: Vec<Breakdown> = Vec::new();
+        for task in tasks {
+            for name in names(task) {
+                let index = match breakdown.iter().position(|b| b.name == name) {
+                    Some(index) => index,
+                    None => {
+                        breakdown.push(Breakdown { name, open: 0, done: 0 });
+                        breakdown.len() - 1
+                    }
+                };
+                if task.done {
+                    breakdown[index].done += 1;
+                } else {
+                    breakdown[index].open += 1;
+                }
+            }
+        }
+        breakdown.sort_by(|a, b| a.name.cmp(&b.name));
+        breakdown
+    }
+}
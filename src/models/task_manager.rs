@@ -1,13 +1,26 @@
+use std::collections::HashMap; // Renumbering provisional IDs
 use std::fs; // Filesystem operations
+use chrono::{Local, Utc}; // Creation and completion timestamps, local due dates
 use serde_json::{from_str, to_string_pretty}; // JSON serialization
-use crate::models::{Task, TaskStats}; // Internal Task and TaskStats structs
+use uuid::Uuid; // Globally unique task identities
+use crate::models::{Task, TaskFilter, TaskStats}; // Internal Task and TaskStats structs, list filters
+use crate::models::task::derived_uuid;
+use crate::models::dates::now; // The current time, or TODO_NOW
+use crate::models::history::{ChangeEntry, HistoryLog}; // Audit log of every mutation
+use crate::models::repo::{commit_message, DataRepo}; // Optional git versioning of the data directory
 
 /// Manages a list of tasks, provides creation, reading, updating, deleting, and listing functionalities.
+/// Updates and deletions work on a set of IDs at once and write the JSON file a single time.
+/// Every task carries a UUID, backfilled on load for tasks stored without one.
 pub struct TaskManager {
     /// In-memory collection of tasks loaded from JSON and updated at runtime.
     pub tasks: Vec<Task>,
     /// Path to the JSON file where tasks are stored.
     json_path: String,
+    /// Append-only log recording every change made through this manager.
+    history: HistoryLog,
+    /// Git repository of the data directory; every change is committed once enabled.
+    repo: DataRepo,
 }
 
 impl TaskManager {
@@ -16,32 +29,73 @@ impl TaskManager {
         // Read the entire file as a string, parse it into a Vec<Task>, and store it in the TaskManager.
         let data = fs::read_to_string(json_path)?;
         let tasks: Vec<Task> = from_str(&data)?;
-        Ok(TaskManager { 
+        let history = HistoryLog::for_store(json_path);
+        let mut task_manager = TaskManager { 
             tasks,
             json_path: json_path.to_string(),
-        })
+            repo: DataRepo::for_store(json_path, history.path()),
+            history,
+        };
+        task_manager.backfill_uuids();
+        Ok(task_manager)
     }
 
-    /// Provides basic stats about the current task list (e.g. total number, last used ID).
+    /// Re-reads the JSON file, picking up changes made by other processes since loading.
+    pub fn reload(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        let data = fs::read_to_string(&self.json_path)?;
+        self.tasks = from_str(&data)?;
+        self.backfill_uuids();
+        Ok(())
+    }
+
+    /// Provides stats about the current task list (e.g. total number, last used ID,
+    /// open and done counts, estimated and remaining effort).
     pub fn get_stats(&self) -> TaskStats {
-        TaskStats {
-            total: self.tasks.len(),
-            last_id: self.tasks.iter().map(|t| t.id).max().unwrap_or(0),
-        }
+        TaskStats::of(&self.tasks, now().with_timezone(&Utc))
     }
 
     /// Creates a new task with a unique ID, adds it to the list, and writes all tasks to JSON.
-    pub fn create_task(&mut self, title: String, description: String) -> Result<Task, Box<dyn std::error::Error>> {
-        // Generate a new task ID by incrementing the highest existing ID.
-        let new_id = self.get_stats().last_id + 1;
-        let task = Task { id: new_id, title, description };
-        self.tasks.push(task.clone());
+    /// Any ID already set on `task` is replaced by the freshly generated one.
+    pub fn create_task(&mut self, task: Task) -> Result<Task, Box<dyn std::error::Error>> {
+        let mut created = self.create_tasks(vec![task])?;
+        Ok(created.remove(0))
+    }
+
+    /// Creates several tasks at once, assigning consecutive IDs and saving a single time.
+    /// A non-zero ID on a draft is provisional: `parent` links to it from other drafts
+    /// of the same batch are rewritten to the ID the draft receives.
+    pub fn create_tasks(&mut self, tasks: Vec<Task>) -> Result<Vec<Task>, Box<dyn std::error::Error>> {
+        // Generate new task IDs above every ID ever issued. Drafts may carry a
+        // provisional ID so other drafts of the batch can name them as parent.
+        let first_id = self.last_issued_id()? + 1;
+        let renumbered: HashMap<u32, u32> = tasks
+            .iter()
+            .enumerate()
+            .filter(|(_, t)| t.id != 0)
+            .map(|(i, t)| (t.id, first_id + i as u32))
+            .collect();
+
+        let mut created = Vec::with_capacity(tasks.len());
+        for (i, mut task) in tasks.into_iter().enumerate() {
+            task.id = first_id + i as u32;
+            if let Some(&parent) = task.parent.as_ref().and_then(|p| renumbered.get(p)) {
+                task.parent = Some(parent);
+            }
+            if task.uuid.is_nil() {
+                task.uuid = Uuid::new_v4();
+            }
+            task.created.get_or_insert_with(|| now().with_timezone(&Utc));
+            stamp_completion(&Task::default(), &mut task);
+            self.tasks.push(task.clone());
+            created.push(task);
+        }
 
         // Serialize the updated tasks vector to JSON, then write it to disk.
-        let json = to_string_pretty(&self.tasks)?;
-        fs::write(&self.json_path, json)?;
+        self.save()?;
+        let transitions: Vec<_> = created.iter().map(|t| (None, Some(t.clone()))).collect();
+        self.record(&transitions)?;
 
-        Ok(task)
+        Ok(created)
     }
 
     /// Obtains a specific task by ID, returning an error if not found.
@@ -54,50 +108,187 @@ impl TaskManager {
         Ok(task.clone())
     }
 
-    /// Updates an existing task and saves changes to disk.
-    pub fn update_task(&mut self, id: u32, title: String, description: String) -> Result<Task, Box<dyn std::error::Error>> {
-        // Find the index of the task matching 'id' for mutable access.
-        let task_index = self.tasks
-            .iter()
-            .position(|t| t.id == id)
-            .ok_or_else(|| format!("Task with id {} not found", id))?;
+    /// Replaces the stored fields of a task with `task`, keeping its ID, and saves changes to disk.
+    pub fn update_task(&mut self, id: u32, task: Task) -> Result<Task, Box<dyn std::error::Error>> {
+        let mut updated = self.modify_tasks(&[id], |existing| {
+            *existing = Task { id, ..task.clone() };
+        })?;
+        Ok(updated.remove(0))
+    }
 
-        // Modify the existing task in place.
-        let task = &mut self.tasks[task_index];
-        task.title = title;
-        task.description = description;
+    /// Applies `change` to every task in `ids`, then saves once.
+    /// Nothing is modified unless all IDs exist, so a bulk update is all-or-nothing.
+    pub fn modify_tasks<F>(&mut self, ids: &[u32], mut change: F) -> Result<Vec<Task>, Box<dyn std::error::Error>>
+    where
+        F: FnMut(&mut Task),
+    {
+        // Resolve every ID up front so a missing task aborts before anything changes.
+        let indexes = self.indexes_of(ids)?;
 
-        // Clone the updated task before writing to disk.
-        let updated_task = task.clone();
-        let json = to_string_pretty(&self.tasks)?;
-        fs::write(&self.json_path, json)?;
+        let mut transitions = Vec::new();
+        for index in indexes {
+            // Keep the previous version around so the change can be recorded.
+            let previous = self.tasks[index].clone();
+            change(&mut self.tasks[index]);
+            stamp_completion(&previous, &mut self.tasks[index]);
+            transitions.push((Some(previous), Some(self.tasks[index].clone())));
+        }
 
-        Ok(updated_task)
+        self.save()?;
+        self.record(&transitions)?;
+
+        Ok(transitions.into_iter().filter_map(|(_, new)| new).collect())
     }
 
-    /// Removes a task from the list by ID, then updates the JSON file.
-    pub fn delete_task(&mut self, id: u32) -> Result<(), Box<dyn std::error::Error>> {
-        // Check if the task exists first, returning an error if it doesn't.
-        self.tasks.iter().find(|t| t.id == id).ok_or("Task not found")?;
-        // Retain only tasks that do not match the provided ID.
-        self.tasks.retain(|task| task.id != id);
+    /// Returns what `modify_tasks` would produce, as (before, after) pairs, without saving.
+    pub fn preview_modify<F>(&self, ids: &[u32], mut change: F) -> Result<Vec<(Task, Task)>, Box<dyn std::error::Error>>
+    where
+        F: FnMut(&mut Task),
+    {
+        let indexes = self.indexes_of(ids)?;
+        Ok(indexes
+            .into_iter()
+            .map(|index| {
+                let before = self.tasks[index].clone();
+                let mut after = before.clone();
+                change(&mut after);
+                stamp_completion(&before, &mut after);
+                (before, after)
+            })
+            .collect())
+    }
+
+    /// Removes every task in `ids` from the list and saves once, returning the removed tasks.
+    /// Nothing is removed unless all IDs exist.
+    pub fn delete_tasks(&mut self, ids: &[u32]) -> Result<Vec<Task>, Box<dyn std::error::Error>> {
+        let indexes = self.indexes_of(ids)?;
+        let removed: Vec<Task> = indexes.iter().map(|&i| self.tasks[i].clone()).collect();
+
+        // Retain only tasks that do not match the provided IDs.
+        self.tasks.retain(|task| !ids.contains(&task.id));
 
         // Save the updated tasks to disk.
-        let json = to_string_pretty(&self.tasks)?;
-        fs::write(&self.json_path, json)?;
+        self.save()?;
+        let transitions: Vec<_> = removed.iter().map(|t| (Some(t.clone()), None)).collect();
+        self.record(&transitions)?;
 
-        Ok(())
+        Ok(removed)
     }
 
     /// Prints a formatted list of all tasks, along with a summary of total tasks.
-    pub fn list_tasks(&self) {
+    pub fn list_tasks(&self, filter: &TaskFilter) {
         println!("\n=== Tasks List ===");
         println!("{:-<50}", "");
-        for task in &self.tasks {
-            println!("{} - {}", task.id, task.title);
+        let mut shown = 0;
+        for task in self.tasks.iter().filter(|t| filter.matches(t)) {
+            let mark = if task.done { "x" } else { " " };
+            let priority = task.priority.map(|p| format!("({}) ", p)).unwrap_or_default();
+            let mut due = task
+                .due
+                .map(|d| format!("  (due {})", d.with_timezone(&Local).format("%Y-%m-%d")))
+                .unwrap_or_default();
+            if let Some(estimate) = task.estimate {
+                due.push_str(&format!("  [est {}]", String::from(estimate)));
+            }
+            if task.tags.is_empty() {
+                println!("{} - [{}] {}{}{}", task.id, mark, priority, task.title, due);
+            } else {
+                println!("{} - [{}] {}{}  #{}{}", task.id, mark, priority, task.title, task.tags.join(" #"), due);
+            }
             println!("{:-<50}", "");
+            shown += 1;
         }
         let stats = self.get_stats();
-        println!("\nTotal Tasks: {}", stats.total);
+        if shown == stats.total {
+            println!("\nTotal Tasks: {}", stats.total);
+        } else {
+            println!("\nShown Tasks: {} of {}", shown, stats.total);
+        }
+    }
+
+    /// The task whose timer is running, if any.
+    pub fn active_timer(&self) -> Option<&Task> {
+        self.tasks.iter().find(|t| t.running_since().is_some())
+    }
+
+    /// Returns every recorded change, oldest first.
+    pub fn history(&self) -> Result<Vec<ChangeEntry>, Box<dyn std::error::Error>> {
+        self.history.entries()
+    }
+
+    /// Returns the recorded changes for a single task, including deleted ones.
+    pub fn task_history(&self, id: u32) -> Result<Vec<ChangeEntry>, Box<dyn std::error::Error>> {
+        let entries = self.history()?;
+        Ok(entries.into_iter().filter(|e| e.task_id == id).collect())
+    }
+
+    /// The git repository of the data directory, used by "sync".
+    pub fn repo(&self) -> &DataRepo {
+        &self.repo
+    }
+
+    /// Serializes the whole task list to JSON and writes it to disk.
+    fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let json = to_string_pretty(&self.tasks)?;
+        fs::write(&self.json_path, json)?;
+        Ok(())
     }
-}
\ No newline at end of file
+
+    /// The highest ID ever given to a task, including deleted ones (as recorded in
+    /// the history log), so numeric IDs are never re-issued.
+    pub fn last_issued_id(&self) -> Result<u32, Box<dyn std::error::Error>> {
+        let recorded = self.history()?.iter().map(|e| e.task_id).max().unwrap_or(0);
+        Ok(recorded.max(self.get_stats().last_id))
+    }
+
+    /// Gives tasks stored before UUIDs existed (or added by hand) their UUID. Only
+    /// the loaded copy changes: the UUID is derived from the ID, so every run
+    /// agrees on it until the next change saves it, and read-only commands never
+    /// rewrite the file.
+    fn backfill_uuids(&mut self) {
+        for task in self.tasks.iter_mut().filter(|t| t.uuid.is_nil()) {
+            task.uuid = derived_uuid(task.id);
+        }
+    }
+
+    /// Maps task IDs to their positions in `tasks`, failing on the first unknown ID.
+    fn indexes_of(&self, ids: &[u32]) -> Result<Vec<usize>, Box<dyn std::error::Error>> {
+        ids.iter()
+            .map(|&id| {
+                self.tasks
+                    .iter()
+                    .position(|t| t.id == id)
+                    .ok_or_else(|| format!("Task with id {} not found", id).into())
+            })
+            .collect()
+    }
+
+    /// Appends the difference between old and new versions of tasks to the audit log,
+    /// then commits both files when the data directory is versioned with git.
+    fn record(&self, transitions: &[(Option<Task>, Option<Task>)]) -> Result<(), Box<dyn std::error::Error>> {
+        let entries: Vec<ChangeEntry> = transitions
+            .iter()
+            .filter_map(|(old, new)| ChangeEntry::between(old.as_ref(), new.as_ref()))
+            .collect();
+        self.history.append(&entries)?;
+
+        // The change is already saved; a failing commit (e.g. git missing) only warns,
+        // on stderr so JSON-RPC and piped output stay clean.
+        if !entries.is_empty() && self.repo.is_enabled() {
+            if let Err(e) = self.repo.commit_all(&commit_message(transitions)) {
+                eprintln!("Warning: could not commit the change: {}", e);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Keeps `completed` in step with `done`: set when a task becomes done (unless the
+/// change supplied its own date, e.g. from an import) and cleared when reopened.
+fn stamp_completion(previous: &Task, task: &mut Task) {
+    if !task.done {
+        task.completed = None;
+    } else if task.completed.is_none() || (!previous.done && task.completed == previous.completed) {
+        task.completed = Some(now().with_timezone(&Utc));
+    }
+}
@@ -0,0 +1,120 @@
+use serde_json::{json, Value};
+use crate::models::{Task, TaskFilter, TaskManager};
+
+// Standard JSON-RPC 2.0 error codes.
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+// Application error codes, in the range reserved for implementations.
+pub const SERVER_ERROR: i64 = -32000;
+pub const TASK_NOT_FOUND: i64 = -32001;
+
+/// An error to be returned to the client as a JSON-RPC error object.
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        RpcError { code, message: message.into() }
+    }
+}
+
+pub fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+/// Handles one request object. Returns the response, or `None` for notifications
+/// (requests without an `id`), which must not be answered. Changes made are
+/// appended to `notifications` as `tasks/changed` parameters.
+pub fn handle(request: &Value, task_manager: &mut TaskManager, notifications: &mut Vec<Value>) -> Option<Value> {
+    let Some(object) = request.as_object() else {
+        return Some(error_response(Value::Null, INVALID_REQUEST, "Request must be an object"));
+    };
+    let id = object.get("id").cloned();
+    let method = object.get("method").and_then(Value::as_str);
+
+    if object.get("jsonrpc").and_then(Value::as_str) != Some("2.0") || method.is_none() {
+        return Some(error_response(id.unwrap_or(Value::Null), INVALID_REQUEST, "Invalid JSON-RPC 2.0 request"));
+    }
+    let params = object.get("params").cloned().unwrap_or(Value::Null);
+
+    let result = call(method.unwrap_or_default(), &params, task_manager, notifications);
+    let id = id?;
+    Some(match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(e) => error_response(id, e.code, &e.message),
+    })
+}
+
+fn call(method: &str, params: &Value, task_manager: &mut TaskManager, notifications: &mut Vec<Value>) -> Result<Value, RpcError> {
+    match method {
+        "tasks/list" => {
+            let filter = match params.get("where").and_then(Value::as_str) {
+                Some(expression) => Some(parse_filter(expression)?),
+                None => None,
+            };
+            let tasks: Vec<&Task> = task_manager
+                .tasks
+                .iter()
+                .filter(|t| filter.as_ref().is_none_or(|f| f.matches(t)))
+                .collect();
+            Ok(json!(tasks))
+        }
+        "tasks/search" => {
+            let query = params.get("query").and_then(Value::as_str)
+                .ok_or_else(|| RpcError::new(INVALID_PARAMS, "Missing 'query'"))?;
+            let filter = parse_filter(query)?;
+            let tasks: Vec<&Task> = task_manager.tasks.iter().filter(|t| filter.matches(t)).collect();
+            Ok(json!(tasks))
+        }
+        "tasks/read" => {
+            let id = task_id(params)?;
+            let task = task_manager.read_task(id).map_err(not_found)?;
+            Ok(json!(task))
+        }
+        "tasks/create" => {
+            let draft = Task::default().patched(params).map_err(|e| RpcError::new(INVALID_PARAMS, e))?;
+            let task = task_manager.create_task(draft).map_err(server_error)?;
+            notifications.push(json!({ "kind": "created", "task": task }));
+            Ok(json!(task))
+        }
+        "tasks/update" => {
+            let id = task_id(params)?;
+            let current = task_manager.read_task(id).map_err(not_found)?;
+            let patched = current.patched(params).map_err(|e| RpcError::new(INVALID_PARAMS, e))?;
+            let task = task_manager.update_task(id, patched).map_err(server_error)?;
+            notifications.push(json!({ "kind": "updated", "task": task }));
+            Ok(json!(task))
+        }
+        "tasks/delete" => {
+            let id = task_id(params)?;
+            task_manager.read_task(id).map_err(not_found)?;
+            let removed = task_manager.delete_tasks(&[id]).map_err(server_error)?;
+            notifications.push(json!({ "kind": "deleted", "task": removed[0] }));
+            Ok(json!(removed[0]))
+        }
+        _ => Err(RpcError::new(METHOD_NOT_FOUND, format!("Method not found: {}", method))),
+    }
+}
+
+fn task_id(params: &Value) -> Result<u32, RpcError> {
+    params.get("id")
+        .and_then(Value::as_u64)
+        .and_then(|id| u32::try_from(id).ok())
+        .ok_or_else(|| RpcError::new(INVALID_PARAMS, "Missing or invalid 'id'"))
+}
+
+fn parse_filter(expression: &str) -> Result<TaskFilter, RpcError> {
+    TaskFilter::parse(expression).map_err(|e| RpcError::new(INVALID_PARAMS, e.to_string()))
+}
+
+fn not_found(e: Box<dyn std::error::Error>) -> RpcError {
+    RpcError::new(TASK_NOT_FOUND, e.to_string())
+}
+
+fn server_error(e: Box<dyn std::error::Error>) -> RpcError {
+    RpcError::new(SERVER_ERROR, e.to_string())
+}
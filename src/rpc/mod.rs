@@ -0,0 +1,165 @@
+///////////////////////////////////////////////////////////////////////////////
+// This 'mod.rs' file is the entry point for the 'rpc' module, the JSON-RPC 2.0
+// channel started by the "rpc" subcommand for editor integrations.
+//
+// 1) 'run' reads messages from stdin and writes replies to stdout until stdin
+//    is closed. Two framings are accepted and answered in kind:
+//    - one JSON message per line, or
+//    - LSP-style "Content-Length: N" headers, a blank line, then N bytes.
+//
+// 2) 'methods.rs' implements the methods (tasks/create, tasks/read,
+//    tasks/update, tasks/delete, tasks/list, tasks/search) on top of the
+//    TaskManager. The store is reloaded before every message so changes made
+//    from the CLI in the meantime are visible.
+//
+// 3) A message that is not UTF-8, or larger than MAX_MESSAGE bytes, is skipped
+//    and answered with an error; the session goes on with the next message.
+//
+// 4) After every successful mutation a "tasks/changed" notification is sent,
+//    letting clients refresh without polling. Batches (JSON arrays) are
+//    supported as described in the JSON-RPC 2.0 specification.
+//
+// stdout carries protocol messages only; diagnostics go to stderr.
+///////////////////////////////////////////////////////////////////////////////
+
+mod methods;
+
+use std::io::{self, BufRead, Read, Write};
+use serde_json::{json, Value};
+use crate::models::TaskManager;
+
+/// The largest message accepted, so a bogus Content-Length cannot exhaust memory.
+const MAX_MESSAGE: usize = 16 * 1024 * 1024;
+
+/// A received message, or the error reply to send when it cannot be used.
+type Incoming = Result<String, Value>;
+
+/// How a message arrived, so the reply can be written the same way.
+#[derive(Clone, Copy)]
+enum Framing {
+    Line,
+    ContentLength,
+}
+
+/// Serves JSON-RPC requests on stdin/stdout until stdin is closed.
+pub fn run(task_manager: &mut TaskManager) -> Result<(), Box<dyn std::error::Error>> {
+    let stdin = io::stdin();
+    let mut input = stdin.lock();
+    let stdout = io::stdout();
+    let mut output = stdout.lock();
+
+    while let Some((framing, message)) = read_message(&mut input)? {
+        let mut outgoing = Vec::new();
+        let text = match message {
+            Ok(text) if text.trim().is_empty() => continue,
+            Ok(text) => text,
+            Err(reply) => {
+                write_message(&mut output, framing, &reply)?;
+                continue;
+            }
+        };
+
+        match task_manager.reload() {
+            Ok(()) => handle_text(&text, task_manager, &mut outgoing),
+            Err(e) => outgoing.push(methods::error_response(Value::Null, methods::SERVER_ERROR, &format!("Could not load tasks: {}", e))),
+        }
+
+        for message in outgoing {
+            write_message(&mut output, framing, &message)?;
+        }
+    }
+    Ok(())
+}
+
+/// Parses a message (single request or batch) and collects the replies and notifications to send.
+fn handle_text(text: &str, task_manager: &mut TaskManager, outgoing: &mut Vec<Value>) {
+    let message: Value = match serde_json::from_str(text) {
+        Ok(message) => message,
+        Err(e) => {
+            outgoing.push(methods::error_response(Value::Null, methods::PARSE_ERROR, &format!("Parse error: {}", e)));
+            return;
+        }
+    };
+
+    let mut notifications = Vec::new();
+    match message {
+        Value::Array(batch) if batch.is_empty() => {
+            outgoing.push(methods::error_response(Value::Null, methods::INVALID_REQUEST, "Empty batch"));
+        }
+        Value::Array(batch) => {
+            let replies: Vec<Value> = batch
+                .iter()
+                .filter_map(|request| methods::handle(request, task_manager, &mut notifications))
+                .collect();
+            if !replies.is_empty() {
+                outgoing.push(Value::Array(replies));
+            }
+        }
+        request => outgoing.extend(methods::handle(&request, task_manager, &mut notifications)),
+    }
+
+    // Change notifications follow the replies of the requests that caused them.
+    outgoing.extend(notifications.into_iter().map(|params| {
+        json!({ "jsonrpc": "2.0", "method": "tasks/changed", "params": params })
+    }));
+}
+
+/// Reads the next message, detecting its framing. Returns `None` at end of input.
+/// A message that cannot be used is consumed and returned as the error reply to send.
+fn read_message(input: &mut impl BufRead) -> Result<Option<(Framing, Incoming)>, Box<dyn std::error::Error>> {
+    let mut line = Vec::new();
+    if input.by_ref().take(MAX_MESSAGE as u64 + 1).read_until(b'\n', &mut line)? == 0 {
+        return Ok(None);
+    }
+    if line.len() > MAX_MESSAGE {
+        input.skip_until(b'\n')?;
+        return Ok(Some((Framing::Line, Err(too_large()))));
+    }
+
+    let Some(length) = std::str::from_utf8(&line).ok().and_then(content_length) else {
+        return Ok(Some((Framing::Line, utf8(line))));
+    };
+
+    // Skip any further headers up to the blank separator line.
+    loop {
+        let mut header = Vec::new();
+        if input.read_until(b'\n', &mut header)? == 0 || header.trim_ascii().is_empty() {
+            break;
+        }
+    }
+    if length > MAX_MESSAGE {
+        io::copy(&mut input.by_ref().take(length as u64), &mut io::sink())?;
+        return Ok(Some((Framing::ContentLength, Err(too_large()))));
+    }
+    // A body cut short by the end of input is answered like any other broken message.
+    let mut body = Vec::with_capacity(length);
+    input.by_ref().take(length as u64).read_to_end(&mut body)?;
+    Ok(Some((Framing::ContentLength, utf8(body))))
+}
+
+fn utf8(bytes: Vec<u8>) -> Incoming {
+    String::from_utf8(bytes)
+        .map_err(|_| methods::error_response(Value::Null, methods::PARSE_ERROR, "Parse error: message is not valid UTF-8"))
+}
+
+fn too_large() -> Value {
+    let message = format!("Message larger than {} bytes", MAX_MESSAGE);
+    methods::error_response(Value::Null, methods::INVALID_REQUEST, &message)
+}
+
+fn content_length(line: &str) -> Option<usize> {
+    let (name, value) = line.split_once(':')?;
+    if !name.trim().eq_ignore_ascii_case("content-length") {
+        return None;
+    }
+    value.trim().parse().ok()
+}
+
+fn write_message(output: &mut impl Write, framing: Framing, message: &Value) -> io::Result<()> {
+    let text = message.to_string();
+    match framing {
+        Framing::Line => writeln!(output, "{}", text)?,
+        Framing::ContentLength => write!(output, "Content-Length: {}\r\n\r\n{}", text.len(), text)?,
+    }
+    output.flush()
+}
@@ -0,0 +1,272 @@
+use std::net::IpAddr;
+use serde_json::{json, Value};
+use crate::models::{Task, TaskFilter, TaskManager};
+
+/// The self-contained web page served at `/`.
+const INDEX_HTML: &str = include_str!("index.html");
+
+/// The parts of an HTTP request the API needs, independent of the server library.
+pub struct ApiRequest {
+    pub method: String,
+    pub url: String,
+    pub if_match: Option<String>,
+    pub if_none_match: Option<String>,
+    pub content_type: Option<String>,
+    pub host: Option<String>,
+    pub origin: Option<String>,
+    pub body: String,
+}
+
+/// A response ready to be written back: status, headers and a (possibly empty) body.
+pub struct ApiResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl ApiResponse {
+    fn json(status: u16, value: &Value) -> Self {
+        ApiResponse {
+            status,
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: value.to_string(),
+        }
+    }
+
+    /// A JSON response for a single task, carrying its ETag.
+    fn task(status: u16, task: &Task) -> Self {
+        let mut response = ApiResponse::json(status, &json!(task));
+        response.headers.push(("ETag".to_string(), etag(task)));
+        response
+    }
+
+    fn html(body: &str) -> Self {
+        ApiResponse {
+            status: 200,
+            headers: vec![("Content-Type".to_string(), "text/html; charset=utf-8".to_string())],
+            body: body.to_string(),
+        }
+    }
+
+    fn empty(status: u16) -> Self {
+        ApiResponse { status, headers: Vec::new(), body: String::new() }
+    }
+
+    pub fn error(status: u16, message: &str) -> Self {
+        ApiResponse::json(status, &json!({ "error": message }))
+    }
+}
+
+/// Routes a request to the matching TaskManager operation.
+pub fn handle(request: &ApiRequest, task_manager: &mut TaskManager) -> ApiResponse {
+    let (path, query) = request.url.split_once('?').unwrap_or((&request.url, ""));
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    if let Some(response) = check_cross_site(request) {
+        return response;
+    }
+
+    match (request.method.as_str(), segments.as_slice()) {
+        ("GET", []) | ("GET", ["index.html"]) => ApiResponse::html(INDEX_HTML),
+        ("GET", ["tasks"]) => list_tasks(query, task_manager),
+        ("POST", ["tasks"]) => create_task(request, task_manager),
+        (_, ["tasks"]) => method_not_allowed("GET, POST"),
+        (method, ["tasks", id]) => {
+            let Ok(id) = id.parse::<u32>() else {
+                return ApiResponse::error(404, "Task not found");
+            };
+            match method {
+                "GET" => read_task(id, request, task_manager),
+                "PATCH" => patch_task(id, request, task_manager),
+                "DELETE" => delete_task(id, request, task_manager),
+                _ => method_not_allowed("GET, PATCH, DELETE"),
+            }
+        }
+        _ => ApiResponse::error(404, "Not found"),
+    }
+}
+
+fn list_tasks(query: &str, task_manager: &TaskManager) -> ApiResponse {
+    let filter = match query_param(query, "where").map(|w| TaskFilter::parse(&w)) {
+        Some(Ok(filter)) => Some(filter),
+        Some(Err(e)) => return ApiResponse::error(400, &e.to_string()),
+        None => None,
+    };
+    // Each task carries its ETag, so clients can send `If-Match` for exactly what they listed.
+    let tasks: Vec<Value> = task_manager
+        .tasks
+        .iter()
+        .filter(|t| filter.as_ref().is_none_or(|f| f.matches(t)))
+        .map(|t| {
+            let mut item = json!(t);
+            item["etag"] = json!(etag(t));
+            item
+        })
+        .collect();
+    ApiResponse::json(200, &Value::Array(tasks))
+}
+
+fn create_task(request: &ApiRequest, task_manager: &mut TaskManager) -> ApiResponse {
+    let task = match parse_body(&request.body).and_then(|patch| Task::default().patched(&patch)) {
+        Ok(task) => task,
+        Err(message) => return ApiResponse::error(400, &message),
+    };
+    match task_manager.create_task(task) {
+        Ok(task) => {
+            let mut response = ApiResponse::task(201, &task);
+            response.headers.push(("Location".to_string(), format!("/tasks/{}", task.id)));
+            response
+        }
+        Err(e) => ApiResponse::error(500, &e.to_string()),
+    }
+}
+
+fn read_task(id: u32, request: &ApiRequest, task_manager: &TaskManager) -> ApiResponse {
+    match task_manager.read_task(id) {
+        Ok(task) if request.if_none_match.as_deref() == Some(etag(&task).as_str()) => {
+            let mut response = ApiResponse::empty(304);
+            response.headers.push(("ETag".to_string(), etag(&task)));
+            response
+        }
+        Ok(task) => ApiResponse::task(200, &task),
+        Err(e) => ApiResponse::error(404, &e.to_string()),
+    }
+}
+
+fn patch_task(id: u32, request: &ApiRequest, task_manager: &mut TaskManager) -> ApiResponse {
+    let current = match task_manager.read_task(id) {
+        Ok(task) => task,
+        Err(e) => return ApiResponse::error(404, &e.to_string()),
+    };
+    if let Some(response) = check_precondition(request, &current) {
+        return response;
+    }
+    let patched = match parse_body(&request.body).and_then(|patch| current.patched(&patch)) {
+        Ok(task) => task,
+        Err(message) => return ApiResponse::error(400, &message),
+    };
+    match task_manager.update_task(id, patched) {
+        Ok(task) => ApiResponse::task(200, &task),
+        Err(e) => ApiResponse::error(500, &e.to_string()),
+    }
+}
+
+fn delete_task(id: u32, request: &ApiRequest, task_manager: &mut TaskManager) -> ApiResponse {
+    let current = match task_manager.read_task(id) {
+        Ok(task) => task,
+        Err(e) => return ApiResponse::error(404, &e.to_string()),
+    };
+    if let Some(response) = check_precondition(request, &current) {
+        return response;
+    }
+    match task_manager.delete_tasks(&[id]) {
+        Ok(_) => ApiResponse::empty(204),
+        Err(e) => ApiResponse::error(500, &e.to_string()),
+    }
+}
+
+/// Rejects writes whose `If-Match` header no longer matches the stored task,
+/// i.e. someone else changed it since the client last read it.
+fn check_precondition(request: &ApiRequest, current: &Task) -> Option<ApiResponse> {
+    let expected = request.if_match.as_deref()?;
+    if expected == "*" || expected == etag(current) {
+        return None;
+    }
+    let mut response = ApiResponse::error(412, "Task was modified by someone else; reload it and try again");
+    response.headers.push(("ETag".to_string(), etag(current)));
+    Some(response)
+}
+
+/// Refuses requests another web site could make a browser send: a `Host` that is
+/// neither `localhost` nor an IP address (DNS rebinding), a write whose `Origin`
+/// is not this server, and a body not sent as `application/json`, which pages on
+/// other sites cannot do without a CORS preflight the server never grants.
+fn check_cross_site(request: &ApiRequest) -> Option<ApiResponse> {
+    let host = request.host.as_deref();
+    if host.is_some_and(|host| !is_local_host(host)) {
+        return Some(ApiResponse::error(403, "Unknown Host; connect through localhost or an IP address"));
+    }
+    let method = request.method.as_str();
+    if matches!(method, "GET" | "HEAD") {
+        return None;
+    }
+    if let Some(origin) = request.origin.as_deref() {
+        if Some(origin) != host.map(|host| format!("http://{}", host)).as_deref() {
+            return Some(ApiResponse::error(403, "Cross-origin requests are not allowed"));
+        }
+    }
+    let json = request
+        .content_type
+        .as_deref()
+        .and_then(|value| value.split(';').next())
+        .is_some_and(|media_type| media_type.trim().eq_ignore_ascii_case("application/json"));
+    if matches!(method, "POST" | "PATCH") && !json {
+        return Some(ApiResponse::error(415, "Content-Type must be application/json"));
+    }
+    None
+}
+
+/// Whether a `Host` header (with an optional port) names this machine by
+/// `localhost` or by an IP address rather than by a domain name.
+fn is_local_host(host: &str) -> bool {
+    let name = match host.strip_prefix('[') {
+        Some(rest) => rest.split(']').next().unwrap_or(""),
+        None => host.rsplit_once(':').map_or(host, |(name, _)| name),
+    };
+    name.eq_ignore_ascii_case("localhost") || name.parse::<IpAddr>().is_ok()
+}
+
+fn method_not_allowed(allowed: &str) -> ApiResponse {
+    let mut response = ApiResponse::error(405, "Method not allowed");
+    response.headers.push(("Allow".to_string(), allowed.to_string()));
+    response
+}
+
+fn parse_body(body: &str) -> Result<Value, String> {
+    match serde_json::from_str::<Value>(body) {
+        Ok(value @ Value::Object(_)) => Ok(value),
+        Ok(_) => Err("Request body must be a JSON object".to_string()),
+        Err(e) => Err(format!("Invalid JSON: {}", e)),
+    }
+}
+
+/// A strong ETag derived from the task's JSON (64-bit FNV-1a), stable across runs.
+fn etag(task: &Task) -> String {
+    let json = serde_json::to_string(task).unwrap_or_default();
+    let hash = json.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("\"{:016x}\"", hash)
+}
+
+/// Returns the percent-decoded value of a query string parameter.
+fn query_param(query: &str, name: &str) -> Option<String> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| percent_decode(value))
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+                match hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+                    Some(byte) => {
+                        decoded.push(byte);
+                        i += 2;
+                    }
+                    None => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
@@ -0,0 +1,84 @@
+///////////////////////////////////////////////////////////////////////////////
+// This 'mod.rs' file is the entry point for the 'server' module, the local
+// HTTP mode started by the "serve" subcommand.
+//
+// 1) 'run' binds a tiny_http server and handles requests one at a time, so the
+//    single TaskManager never sees concurrent mutations. Before each request
+//    the store is reloaded from disk, which keeps the API in step with changes
+//    made through the CLI in the meantime.
+//
+// 2) 'api.rs' maps each request to a TaskManager call and builds the JSON
+//    response, including status codes and ETag handling. Requests another web
+//    site could trigger from a browser are refused first: writes must be JSON
+//    from this server's own origin, and the Host must be localhost or an IP.
+//
+// 3) 'index.html' is a self-contained web page (no external scripts or styles)
+//    embedded in the binary and served at '/'. It uses the endpoints below to
+//    list, create, edit, complete and delete tasks.
+//
+// Endpoints:
+//   GET    /               the web interface
+//   GET    /tasks          list every task (optional ?where=<filter>)
+//   POST   /tasks          create a task from a JSON body
+//   GET    /tasks/{id}     read one task
+//   PATCH  /tasks/{id}     change some fields of a task
+//   DELETE /tasks/{id}     delete a task
+///////////////////////////////////////////////////////////////////////////////
+
+mod api;
+
+use tiny_http::{Header, Response, Server};
+use crate::models::TaskManager;
+use api::ApiRequest;
+
+/// Serves the REST API on `address` (e.g. "127.0.0.1:8080") until the process is stopped.
+pub fn run(address: &str, task_manager: &mut TaskManager) -> Result<(), Box<dyn std::error::Error>> {
+    let server = Server::http(address).map_err(|e| format!("Could not listen on {}: {}", address, e))?;
+    println!("Listening on http://{} (Ctrl-C to stop)", address);
+    println!("Open http://{}/ in a browser for the web interface", address);
+
+    for mut request in server.incoming_requests() {
+        let mut body = String::new();
+        let api_request = match request.as_reader().read_to_string(&mut body) {
+            Ok(_) => ApiRequest {
+                method: request.method().as_str().to_uppercase(),
+                url: request.url().to_string(),
+                if_match: header(&request, "If-Match"),
+                if_none_match: header(&request, "If-None-Match"),
+                content_type: header(&request, "Content-Type"),
+                host: header(&request, "Host"),
+                origin: header(&request, "Origin"),
+                body,
+            },
+            Err(e) => {
+                println!("Failed to read request body: {}", e);
+                continue;
+            }
+        };
+
+        let response = match task_manager.reload() {
+            Ok(()) => api::handle(&api_request, task_manager),
+            Err(e) => api::ApiResponse::error(500, &format!("Could not load tasks: {}", e)),
+        };
+        println!("{} {} -> {}", api_request.method, api_request.url, response.status);
+
+        let mut reply = Response::from_string(response.body).with_status_code(response.status);
+        for (name, value) in response.headers {
+            if let Ok(header) = Header::from_bytes(name.as_bytes(), value.as_bytes()) {
+                reply.add_header(header);
+            }
+        }
+        if let Err(e) = request.respond(reply) {
+            println!("Failed to send response: {}", e);
+        }
+    }
+    Ok(())
+}
+
+fn header(request: &tiny_http::Request, name: &str) -> Option<String> {
+    request
+        .headers()
+        .iter()
+        .find(|h| h.field.as_str().as_str().eq_ignore_ascii_case(name))
+        .map(|h| h.value.as_str().to_string())
+}
@@ -0,0 +1,253 @@
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::widgets::ListState;
+use crate::models::{Task, TaskFilter, TaskManager};
+
+/// What the keyboard is currently driving.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Moving through the list and triggering actions
+    Normal,
+    /// Typing a search; the list narrows with every key
+    Search,
+    /// Typing a `--where` style filter expression, applied on Enter
+    Filter,
+    /// Typing the title of a new task
+    Create,
+    /// Waiting for y/n before deleting the selected task
+    ConfirmDelete,
+}
+
+/// Work the event loop must do outside of the full-screen interface.
+pub enum Action {
+    /// Open the task in $EDITOR
+    Edit(u32),
+}
+
+/// State of the interactive interface. Tasks themselves stay in the TaskManager;
+/// the app only remembers which IDs are visible and which one is selected.
+pub struct App {
+    pub mode: Mode,
+    pub list_state: ListState,
+    /// IDs of the tasks passing the current filter and search, in list order.
+    pub visible: Vec<u32>,
+    pub search: String,
+    pub filter_text: String,
+    pub input: String,
+    pub status: String,
+    pub should_quit: bool,
+    filter: Option<TaskFilter>,
+}
+
+impl App {
+    pub fn new(task_manager: &TaskManager) -> Self {
+        let mut app = App {
+            mode: Mode::Normal,
+            list_state: ListState::default(),
+            visible: Vec::new(),
+            search: String::new(),
+            filter_text: String::new(),
+            input: String::new(),
+            status: String::new(),
+            should_quit: false,
+            filter: None,
+        };
+        app.refresh(task_manager);
+        app
+    }
+
+    /// Recomputes the visible tasks and keeps the selection within bounds.
+    pub fn refresh(&mut self, task_manager: &TaskManager) {
+        let search = self.search.to_lowercase();
+        self.visible = task_manager
+            .tasks
+            .iter()
+            .filter(|t| self.filter.as_ref().is_none_or(|f| f.matches(t)))
+            .filter(|t| {
+                search.is_empty()
+                    || t.title.to_lowercase().contains(&search)
+                    || t.description.to_lowercase().contains(&search)
+            })
+            .map(|t| t.id)
+            .collect();
+
+        let selected = match self.list_state.selected() {
+            _ if self.visible.is_empty() => None,
+            Some(i) => Some(i.min(self.visible.len() - 1)),
+            None => Some(0),
+        };
+        self.list_state.select(selected);
+    }
+
+    pub fn selected_task<'a>(&self, task_manager: &'a TaskManager) -> Option<&'a Task> {
+        let id = self.list_state.selected().and_then(|i| self.visible.get(i))?;
+        task_manager.tasks.iter().find(|t| t.id == *id)
+    }
+
+    pub fn set_status(&mut self, status: String) {
+        self.status = status;
+    }
+
+    /// Applies a key press, returning an action the event loop must carry out.
+    pub fn handle_key(&mut self, key: KeyEvent, task_manager: &mut TaskManager) -> Option<Action> {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            self.should_quit = true;
+            return None;
+        }
+
+        match self.mode {
+            Mode::Normal => return self.handle_normal(key, task_manager),
+            Mode::Search => self.handle_search(key, task_manager),
+            Mode::Filter | Mode::Create => self.handle_input(key, task_manager),
+            Mode::ConfirmDelete => self.handle_confirm(key, task_manager),
+        }
+        None
+    }
+
+    fn handle_normal(&mut self, key: KeyEvent, task_manager: &mut TaskManager) -> Option<Action> {
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => self.should_quit = true,
+            KeyCode::Down | KeyCode::Char('j') => self.move_by(1),
+            KeyCode::Up | KeyCode::Char('k') => self.move_by(-1),
+            KeyCode::PageDown => self.move_by(10),
+            KeyCode::PageUp => self.move_by(-10),
+            KeyCode::Home | KeyCode::Char('g') => self.move_by(isize::MIN),
+            KeyCode::End | KeyCode::Char('G') => self.move_by(isize::MAX),
+            KeyCode::Char('/') => {
+                self.mode = Mode::Search;
+                self.status = "Search: type to narrow the list, Enter to keep, Esc to clear".to_string();
+            }
+            KeyCode::Char('f') => {
+                self.mode = Mode::Filter;
+                self.input = self.filter_text.clone();
+                self.status = "Filter, e.g. tag:sprint-4 done:no (empty to clear)".to_string();
+            }
+            KeyCode::Char('n') => {
+                self.mode = Mode::Create;
+                self.input.clear();
+                self.status = "Title of the new task".to_string();
+            }
+            KeyCode::Char('e') | KeyCode::Enter => {
+                return self.selected_task(task_manager).map(|t| Action::Edit(t.id));
+            }
+            KeyCode::Char(' ') | KeyCode::Char('x') => self.toggle_done(task_manager),
+            KeyCode::Char('d') | KeyCode::Delete => {
+                if let Some(task) = self.selected_task(task_manager) {
+                    self.status = format!("Delete task {} \"{}\"? (y/n)", task.id, task.title);
+                    self.mode = Mode::ConfirmDelete;
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn handle_search(&mut self, key: KeyEvent, task_manager: &TaskManager) {
+        match key.code {
+            KeyCode::Enter => {
+                self.mode = Mode::Normal;
+                self.status.clear();
+            }
+            KeyCode::Esc => {
+                self.search.clear();
+                self.mode = Mode::Normal;
+                self.status.clear();
+            }
+            KeyCode::Backspace => {
+                self.search.pop();
+            }
+            KeyCode::Char(c) => self.search.push(c),
+            _ => {}
+        }
+        self.refresh(task_manager);
+    }
+
+    fn handle_input(&mut self, key: KeyEvent, task_manager: &mut TaskManager) {
+        match key.code {
+            KeyCode::Esc => {
+                self.mode = Mode::Normal;
+                self.status.clear();
+            }
+            KeyCode::Backspace => {
+                self.input.pop();
+            }
+            KeyCode::Char(c) => self.input.push(c),
+            KeyCode::Enter => {
+                let input = std::mem::take(&mut self.input);
+                self.status = match self.mode {
+                    Mode::Filter => self.apply_filter(input.trim()),
+                    _ => self.create(input.trim(), task_manager),
+                };
+                self.mode = Mode::Normal;
+                self.refresh(task_manager);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_confirm(&mut self, key: KeyEvent, task_manager: &mut TaskManager) {
+        self.mode = Mode::Normal;
+        if !matches!(key.code, KeyCode::Char('y') | KeyCode::Char('Y')) {
+            self.status = "Delete cancelled".to_string();
+            return;
+        }
+        let Some(id) = self.selected_task(task_manager).map(|t| t.id) else { return };
+        self.status = match task_manager.delete_tasks(&[id]) {
+            Ok(_) => format!("Task {} deleted", id),
+            Err(e) => format!("Error: {}", e),
+        };
+        self.refresh(task_manager);
+    }
+
+    fn apply_filter(&mut self, expression: &str) -> String {
+        if expression.is_empty() {
+            self.filter = None;
+            self.filter_text.clear();
+            return "Filter cleared".to_string();
+        }
+        match TaskFilter::parse(expression) {
+            Ok(filter) => {
+                self.filter = Some(filter);
+                self.filter_text = expression.to_string();
+                format!("Filter: {}", expression)
+            }
+            Err(e) => format!("Error: {}", e),
+        }
+    }
+
+    fn create(&mut self, title: &str, task_manager: &mut TaskManager) -> String {
+        if title.is_empty() {
+            return "Create cancelled".to_string();
+        }
+        let task = Task { title: title.to_string(), ..Task::default() };
+        match task_manager.create_task(task) {
+            Ok(task) => {
+                self.refresh(task_manager);
+                if let Some(i) = self.visible.iter().position(|id| *id == task.id) {
+                    self.list_state.select(Some(i));
+                }
+                format!("Task {} created (press e to add a description)", task.id)
+            }
+            Err(e) => format!("Failed to create task: {}", e),
+        }
+    }
+
+    fn toggle_done(&mut self, task_manager: &mut TaskManager) {
+        let Some(task) = self.selected_task(task_manager) else { return };
+        let (id, done) = (task.id, !task.done);
+        self.status = match task_manager.modify_tasks(&[id], |t| t.done = done) {
+            Ok(_) => format!("Task {} marked as {}", id, if done { "done" } else { "open" }),
+            Err(e) => format!("Error: {}", e),
+        };
+        self.refresh(task_manager);
+    }
+
+    fn move_by(&mut self, delta: isize) {
+        if self.visible.is_empty() {
+            return;
+        }
+        let last = self.visible.len() as isize - 1;
+        let current = self.list_state.selected().unwrap_or(0) as isize;
+        let next = current.saturating_add(delta).clamp(0, last);
+        self.list_state.select(Some(next as usize));
+    }
+}
@@ -0,0 +1,70 @@
+///////////////////////////////////////////////////////////////////////////////
+// This 'mod.rs' file is the entry point for the 'tui' module, the full-screen
+// terminal interface started by the "tui" subcommand.
+//
+// 1) 'app.rs' holds the interface state (visible tasks, selection, search text,
+//    filter, input mode) and turns key presses into TaskManager calls.
+//
+// 2) 'ui.rs' draws that state with ratatui: the task list, the detail pane and
+//    the status/help lines at the bottom.
+//
+// 3) 'run' below owns the terminal: it switches to the alternate screen, runs
+//    the draw → read key → update loop, and always restores the terminal, even
+//    when an error occurs. Editing a task temporarily leaves the full-screen
+//    mode so $EDITOR can take over the terminal.
+///////////////////////////////////////////////////////////////////////////////
+
+mod app;
+mod ui;
+
+use ratatui::crossterm::event::{self, Event, KeyEventKind};
+use ratatui::DefaultTerminal;
+use crate::commands::edit::edit_in_editor;
+use crate::models::TaskManager;
+use app::{Action, App};
+
+/// Runs the interactive interface until the user quits.
+pub fn run(task_manager: &mut TaskManager) -> Result<(), Box<dyn std::error::Error>> {
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, task_manager);
+    ratatui::restore();
+    result
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, task_manager: &mut TaskManager) -> Result<(), Box<dyn std::error::Error>> {
+    let mut app = App::new(task_manager);
+
+    while !app.should_quit {
+        terminal.draw(|frame| ui::draw(frame, &mut app, task_manager))?;
+
+        let key = match event::read()? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => key,
+            _ => continue,
+        };
+
+        if let Some(Action::Edit(id)) = app.handle_key(key, task_manager) {
+            // Hand the terminal over to the editor, then take it back.
+            ratatui::restore();
+            let outcome = edit_task(id, task_manager);
+            *terminal = ratatui::init();
+            app.set_status(outcome);
+            app.refresh(task_manager);
+        }
+    }
+    Ok(())
+}
+
+fn edit_task(id: u32, task_manager: &mut TaskManager) -> String {
+    let task = match task_manager.read_task(id) {
+        Ok(task) => task,
+        Err(e) => return format!("Error: {}", e),
+    };
+    match edit_in_editor(&task) {
+        Ok(Some(edited)) => match task_manager.update_task(id, edited) {
+            Ok(task) => format!("Task {} updated", task.id),
+            Err(e) => format!("Error updating task: {}", e),
+        },
+        Ok(None) => format!("Edit cancelled, task {} unchanged", id),
+        Err(e) => format!("Error: {}", e),
+    }
+}
@@ -0,0 +1,101 @@
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, List, ListItem, Paragraph, Wrap};
+use ratatui::Frame;
+use crate::formats::display_timestamp;
+use crate::models::TaskManager;
+use super::app::{App, Mode};
+
+const HELP: &str = "↑/↓ move  / search  f filter  n new  e edit  space done  d delete  q quit";
+
+/// Draws the whole screen: task list and details side by side, then the
+/// input/status line and the key help.
+pub fn draw(frame: &mut Frame, app: &mut App, task_manager: &TaskManager) {
+    let [main, status, help] = Layout::vertical([
+        Constraint::Min(3),
+        Constraint::Length(1),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+    let [list_area, detail_area] = Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .areas(main);
+
+    // Task list
+    let items: Vec<ListItem> = app
+        .visible
+        .iter()
+        .filter_map(|id| task_manager.tasks.iter().find(|t| t.id == *id))
+        .map(|task| {
+            let mark = if task.done { "[x]" } else { "[ ]" };
+            let mut spans = vec![Span::raw(format!("{} {:>3} {}", mark, task.id, task.title))];
+            if !task.tags.is_empty() {
+                spans.push(Span::raw(format!("  #{}", task.tags.join(" #"))).dim());
+            }
+            let line = Line::from(spans);
+            if task.done {
+                ListItem::new(line.style(Style::new().add_modifier(Modifier::DIM)))
+            } else {
+                ListItem::new(line)
+            }
+        })
+        .collect();
+
+    let mut title = format!(" Tasks {}/{} ", app.visible.len(), task_manager.tasks.len());
+    if !app.filter_text.is_empty() {
+        title.push_str(&format!("[filter: {}] ", app.filter_text));
+    }
+    if !app.search.is_empty() {
+        title.push_str(&format!("[search: {}] ", app.search));
+    }
+
+    let list = List::new(items)
+        .block(Block::bordered().title(title))
+        .highlight_style(Style::new().reversed())
+        .highlight_symbol("> ");
+    frame.render_stateful_widget(list, list_area, &mut app.list_state);
+
+    // Detail pane
+    let details = match app.selected_task(task_manager) {
+        Some(task) => {
+            let mut lines = vec![
+                Line::from(vec!["ID: ".bold(), Span::raw(task.id.to_string())]),
+                Line::from(vec!["Title: ".bold(), Span::raw(task.title.clone())]),
+                Line::from(vec!["Status: ".bold(), Span::raw(if task.done { "done" } else { "open" })]),
+            ];
+            if let Some(priority) = task.priority {
+                lines.push(Line::from(vec!["Priority: ".bold(), Span::raw(priority.to_string())]));
+            }
+            if let Some(project) = &task.project {
+                lines.push(Line::from(vec!["Project: ".bold(), Span::raw(project.clone())]));
+            }
+            if !task.tags.is_empty() {
+                lines.push(Line::from(vec!["Tags: ".bold(), Span::raw(task.tags.join(", "))]));
+            }
+            if let Some(due) = task.due {
+                lines.push(Line::from(vec!["Due: ".bold(), Span::raw(display_timestamp(due))]));
+            }
+            if let Some(scheduled) = task.scheduled {
+                lines.push(Line::from(vec!["Scheduled: ".bold(), Span::raw(display_timestamp(scheduled))]));
+            }
+            lines.push(Line::from(""));
+            lines.extend(task.description.lines().map(|l| Line::from(l.to_string())));
+            lines
+        }
+        None => vec![Line::from("No task selected")],
+    };
+    let detail = Paragraph::new(details)
+        .block(Block::bordered().title(" Details "))
+        .wrap(Wrap { trim: false });
+    frame.render_widget(detail, detail_area);
+
+    // Input or status line
+    let status_line = match app.mode {
+        Mode::Search => Line::from(vec!["/".bold(), Span::raw(app.search.clone())]),
+        Mode::Filter => Line::from(vec!["filter: ".bold(), Span::raw(app.input.clone())]),
+        Mode::Create => Line::from(vec!["new task: ".bold(), Span::raw(app.input.clone())]),
+        Mode::Normal | Mode::ConfirmDelete => Line::from(app.status.clone()),
+    };
+    frame.render_widget(Paragraph::new(status_line), status);
+    frame.render_widget(Paragraph::new(Line::from(HELP).dim()), help);
+}